@@ -1,4 +1,5 @@
 pub mod types;
+pub mod timer;
 
 use embedded_hal::spi::{Mode, MODE_0};
 use rp2040_hal::pac::{Peripherals, SIO, USBCTRL_REGS, USBCTRL_DPRAM, RESETS, IO_BANK0, PADS_BANK0};
@@ -0,0 +1,30 @@
+use core::future::Future;
+use embedded_hal_async::delay::DelayNs;
+use rp2040_hal::fugit::ExtU64;
+use rtic_monotonics::Monotonic;
+use crate::Mono;
+use sx1280::timer::{Timeout, TimeoutError};
+
+/// Driver time source backed by the RP2040 timer monotonic
+#[derive(Clone, Copy)]
+pub struct MonoTimer;
+
+impl DelayNs for MonoTimer {
+    async fn delay_ns(&mut self, ns: u32) {
+        Mono::delay((ns as u64).div_ceil(1000).micros()).await;
+    }
+
+    async fn delay_us(&mut self, us: u32) {
+        Mono::delay((us as u64).micros()).await;
+    }
+
+    async fn delay_ms(&mut self, ms: u32) {
+        Mono::delay((ms as u64).millis()).await;
+    }
+}
+
+impl Timeout for MonoTimer {
+    async fn timeout_ms<F: Future>(&mut self, ms: u64, future: F) -> Result<F::Output, TimeoutError> {
+        Mono::timeout_after(ms.millis(), future).await.map_err(|_| TimeoutError)
+    }
+}
@@ -4,8 +4,6 @@
 mod boot;
 mod bsp;
 mod cdc;
-mod spi;
-mod sx1280;
 
 use defmt_rtt as _;
 use panic_probe as _;
@@ -28,28 +26,24 @@ mod app {
     use rtic_sync::make_channel;
     use usb_device::class_prelude::*;
     use crate::bsp::{Board, types::*};
+    use crate::bsp::timer::MonoTimer;
     use crate::cdc::CDCDevice;
-    use crate::spi::SpiDevice;
-    use crate::sx1280::commands::clear_irq::ClearIrqCommand;
-    use crate::sx1280::{SX1280Error, SX1280};
-    use crate::sx1280::commands::get_status::GetStatusCommand;
-    use crate::sx1280::commands::set_buffer_base_address::SetBufferBaseAddressCommand;
-    use crate::sx1280::commands::set_modulation_parameters::{Bandwidth, CodingRate, SetLoraModulationParameters, SpreadingFactor};
-    use crate::sx1280::commands::set_packet_parameters::{LoRaCrcMode, LoRaHeaderType, LoRaIQMode, SetLoraPacketParameters};
-    use crate::sx1280::commands::set_packet_type::SetPacketTypeCommand;
-    use crate::sx1280::commands::set_rf_frequency::{IntoRFFrequency, SetRFFrequencyCommand};
-    use crate::sx1280::commands::set_tx::{SetTxModeCommand, TxPeriod};
-    use crate::sx1280::commands::set_tx_parameters::{SetTxParametersCommand, TxRampTime};
-    use crate::sx1280::commands::{PeriodBase, SX1280Interrupt};
-    use crate::sx1280::commands::get_irq_status::GetIrqStatusCommand;
-    use crate::sx1280::commands::set_irq_params::SetIRQParametersCommand;
-    use crate::sx1280::commands::set_rx::{RxPeriod, SetRxModeCommand};
-    use crate::sx1280::commands::set_standby::{SetStandbyModeCommand, StandbyMode};
-    use crate::sx1280::lora::ModeLoRa;
-    use crate::sx1280::registers::frequency_compensation_mode::FrequencyCompensationMode;
-    use crate::sx1280::registers::rx_gain::{RxGain, RxGainSensitivity};
-    use crate::sx1280::registers::sf_additional_configuration::SFAdditionalConfiguration;
-    use crate::sx1280::uninitialized::ModeUninitialized;
+    use sx1280::spi::SpiDevice;
+    use sx1280::commands::clear_irq::ClearIrqCommand;
+    use sx1280::{SX1280Error, SX1280};
+    use sx1280::commands::get_status::GetStatusCommand;
+    use sx1280::commands::set_modulation_parameters::{Bandwidth, CodingRate, SpreadingFactor};
+    use sx1280::commands::set_packet_parameters::{LoRaCrcMode, LoRaHeaderType, LoRaIQMode};
+    use sx1280::commands::set_packet_type::SetPacketTypeCommand;
+    use sx1280::commands::set_tx_parameters::TxRampTime;
+    use sx1280::commands::{PeriodBase, SX1280Interrupt};
+    use sx1280::commands::get_irq_status::GetIrqStatusCommand;
+    use sx1280::commands::set_rx::{RxPeriod, SetRxModeCommand};
+    use sx1280::commands::set_standby::{SetStandbyModeCommand, StandbyMode};
+    use sx1280::dio::NoDio;
+    use sx1280::lora::{LoRaConfig, ModeLoRa};
+    use sx1280::registers::rx_gain::{RxGain, RxGainSensitivity};
+    use sx1280::uninitialized::ModeUninitialized;
 
     #[shared]
     struct Shared {
@@ -83,11 +77,11 @@ mod app {
         trace!("Init SPI devices");
         let spi_a = cx.local.spi_bus_a.write(board.spi_0);
         let sx_a = SpiDevice::new(spi_a, board.pin_cs_a).ok().unwrap();
-        let sx_a_dev = SX1280::new(sx_a, board.pin_busy_a, board.pin_reset_a).ok().unwrap();
+        let sx_a_dev = SX1280::new(sx_a, board.pin_busy_a, board.pin_reset_a, NoDio, MonoTimer).ok().unwrap();
 
         let spi_b = cx.local.spi_bus_b.write(board.spi_1);
         let sx_b = SpiDevice::new(spi_b, board.pin_cs_b).ok().unwrap();
-        let sx_b_dev = SX1280::new(sx_b, board.pin_busy_b, board.pin_reset_b).ok().unwrap();
+        let sx_b_dev = SX1280::new(sx_b, board.pin_busy_b, board.pin_reset_b, NoDio, MonoTimer).ok().unwrap();
 
         let (uart_recv, uart_rx_queue) = make_channel!(u8, 32);
         let (uart_send, uart_tx_queue) = make_channel!(u8, 32);
@@ -130,8 +124,8 @@ mod app {
         _: usb_rx::Context,
         mut rx_queue: Receiver<'static, u8, 32>,
         mut uart_tx: Sender<'static, u8, 32>,
-        sx_a: SX1280<'static, Spi0, PinCSA, PinBusyA, PinResetA, ModeUninitialized>,
-        sx_b: SX1280<'static, Spi1, PinCSB, PinBusyB, PinResetB, ModeUninitialized>
+        sx_a: SX1280<'static, Spi0, PinCSA, PinBusyA, PinResetA, NoDio, MonoTimer, ModeUninitialized>,
+        sx_b: SX1280<'static, Spi1, PinCSB, PinBusyB, PinResetB, NoDio, MonoTimer, ModeUninitialized>
     ) {
 
         trace!("Resetting...");
@@ -150,65 +144,37 @@ mod app {
         tx.wait_for_busy(1000).await.ok().unwrap();
         rx.wait_for_busy(1000).await.ok().unwrap();
 
-        info!("TX Config...");
-        //tx_config
-        {
-            tx.command_and_wait(SetRFFrequencyCommand(2_495_000_000), 1000).await.ok().unwrap();
-            tx.command_and_wait(SetBufferBaseAddressCommand { rx_base_address: 0, tx_base_address: 128 }, 1000).await.ok().unwrap();
-            tx.command_and_wait(SetLoraModulationParameters {
-                bandwidth: Bandwidth::BW203k125Hz,
-                coding_rate: CodingRate::CR4_7,
-                spreading_factor: SpreadingFactor::SF7
-            }, 1000).await.ok().unwrap();
-            tx.write_register(SFAdditionalConfiguration::SF7_8).await.ok().unwrap();
-            tx.write_register(FrequencyCompensationMode(1)).await.ok().unwrap();
-            tx.command_and_wait(SetLoraPacketParameters {
-                crc_mode: LoRaCrcMode::Enabled,
-                header_type: LoRaHeaderType::Explicit,
-                iq_mode: LoRaIQMode::Standard,
-                payload_length: 64,
-                preamble_length: 20.into(),
-            }, 1000).await.ok().unwrap();
-            tx.command_and_wait(SetTxParametersCommand {
-                power: -18,
-                ramp: TxRampTime::Ramp10us
-            }, 1000).await.ok().unwrap();
-            tx.write_buffer(128, &[0, 1, 2, 8, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7]).await.ok().unwrap();
-            tx.command_and_wait(SetIRQParametersCommand {
-                dio_mask: [SX1280Interrupt::empty(); 3],
-                irq_mask: SX1280Interrupt::TxDone | SX1280Interrupt::RXTXTimeout,
-            }, 1000).await.ok().unwrap();
-            tx.command_and_wait(ClearIrqCommand(SX1280Interrupt::all()), 1000).await.ok().unwrap();
-        }
+        let tx_config = LoRaConfig {
+            frequency: 2_495_000_000,
+            tx_base_address: 128,
+            rx_base_address: 0,
+            spreading_factor: SpreadingFactor::SF7,
+            bandwidth: Bandwidth::BW203k125Hz,
+            coding_rate: CodingRate::CR4_7,
+            preamble_length: 20,
+            header_type: LoRaHeaderType::Explicit,
+            payload_length: 64,
+            crc_mode: LoRaCrcMode::Enabled,
+            iq_mode: LoRaIQMode::Standard,
+            power: -18,
+            ramp: TxRampTime::Ramp10us,
+            irq_mask: SX1280Interrupt::TxDone | SX1280Interrupt::RXTXTimeout,
+        };
+        let rx_config = LoRaConfig {
+            preamble_length: 10,
+            payload_length: 32,
+            irq_mask: SX1280Interrupt::RxDone | SX1280Interrupt::RXTXTimeout | SX1280Interrupt::CRCError,
+            ..tx_config
+        };
 
+        info!("TX Config...");
+        tx.configure(&tx_config).await.ok().unwrap();
         info!("RX Config...");
-        //rx_config
-        {
-            rx.command_and_wait(SetRFFrequencyCommand(2_495_000_000), 1000).await.ok().unwrap();
-            rx.command_and_wait(SetBufferBaseAddressCommand { rx_base_address: 0, tx_base_address: 128 }, 1000).await.ok().unwrap();
-            rx.command_and_wait(SetLoraModulationParameters {
-                bandwidth: Bandwidth::BW203k125Hz,
-                coding_rate: CodingRate::CR4_7,
-                spreading_factor: SpreadingFactor::SF7
-            }, 1000).await.ok().unwrap();
-            rx.write_register(SFAdditionalConfiguration::SF7_8).await.ok().unwrap();
-            rx.write_register(FrequencyCompensationMode(1)).await.ok().unwrap();
-            rx.command_and_wait(SetLoraPacketParameters {
-                crc_mode: LoRaCrcMode::Enabled,
-                header_type: LoRaHeaderType::Explicit,
-                iq_mode: LoRaIQMode::Standard,
-                payload_length: 32,
-                preamble_length: 10.into(),
-            }, 1000).await.ok().unwrap();
-            rx.command_and_wait(SetIRQParametersCommand {
-                dio_mask: [SX1280Interrupt::empty(); 3],
-                irq_mask: SX1280Interrupt::RxDone | SX1280Interrupt::RXTXTimeout | SX1280Interrupt::CRCError,
-            }, 1000).await.ok().unwrap();
-            rx.command_and_wait(ClearIrqCommand(SX1280Interrupt::all()), 1000).await.ok().unwrap();
-            // rx.write_register(RxGain::new().with_sensitivity(RxGainSensitivity::LowSensitivity)).await.ok().unwrap();
-        }
+        rx.configure(&rx_config).await.ok().unwrap();
+        // rx.modify_register(|gain: RxGain| gain.with_sensitivity(RxGainSensitivity::LowSensitivity)).await.ok().unwrap();
 
-        info!("Config Complete!");
+        let expected_duration = tx_config.time_on_air(64).ok().unwrap() / 1_000_000;
+        info!("Config Complete! (packets take {}ms on air)", expected_duration);
 
         let mut x = 0;
         loop {
@@ -225,13 +191,7 @@ mod app {
 
 
             let current_time = Mono::now();
-            tx.command_and_wait(SetTxModeCommand {
-                period: TxPeriod::NoTimeout,
-                period_base: PeriodBase::Base1ms
-            }, 10000).await.ok().unwrap();
-
-
-            let response = tx.wait_for_irq(SX1280Interrupt::TxDone, true, 10000).await;
+            let response = tx.transmit(&[0, 1, 2, 8, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7], 10000).await;
             match response {
                 Ok(_) => {}
                 Err(SX1280Error::Timeout) => {error!("timeout")}
@@ -239,7 +199,7 @@ mod app {
                 Err(_) => {error!("other")}
             }
             let duration = Mono::now().checked_duration_since(current_time).unwrap().to_millis();
-            info!("{} - Transmitted (took {}ms)", x, duration);
+            info!("{} - Transmitted (took {}ms, {}ms on air)", x, duration, expected_duration);
             x += 1;
 
             Mono::delay(1000.millis()).await;
@@ -0,0 +1,199 @@
+//! Commands checked against frames taken from the datasheet, opcode first.
+//! Responses are given as the status byte and the payload clocked out after it.
+
+use std::vec::Vec;
+use crate::SX1280Mode;
+use crate::ble::ModeBLE;
+use crate::flrc::ModeFLRC;
+use crate::gfsk::ModeGFSK;
+use crate::lora::ModeLoRa;
+#[cfg(feature = "ranging")]
+use crate::ranging::ModeLoRaRanging;
+use crate::commands::{PeriodBase, SX1280Command, SX1280Interrupt};
+use crate::commands::clear_irq::ClearIrqCommand;
+use crate::commands::get_instantaneous_rssi::GetInstantaneousRssiCommand;
+use crate::commands::get_irq_status::GetIrqStatusCommand;
+use crate::commands::get_packet_status::{GetPacketStatusCommand, PacketErrors, PacketStatusFlags};
+use crate::commands::get_packet_type::GetPacketTypeCommand;
+use crate::commands::get_rx_buffer_status::GetRxBufferStatusCommand;
+use crate::commands::get_status::{CircuitMode, CommandStatus, GetStatusCommand, Status};
+use crate::commands::set_auto_fs::SetAutoFsCommand;
+use crate::commands::set_auto_tx::SetAutoTxCommand;
+use crate::commands::set_buffer_base_address::SetBufferBaseAddressCommand;
+use crate::commands::set_cad::SetCAD;
+use crate::commands::set_cad_parameters::{CadSymbolsNumber, SetCadParametersCommand};
+use crate::commands::set_fs::SetFrequencySynthesisModeCommand;
+use crate::commands::set_irq_params::SetIRQParametersCommand;
+use crate::commands::set_long_preamble::SetLongPreambleModeCommand;
+use crate::commands::set_modulation_parameters::*;
+use crate::commands::set_packet_parameters::*;
+use crate::commands::set_packet_type::{PacketType, SetPacketTypeCommand};
+#[cfg(feature = "ranging")]
+use crate::commands::set_ranging_role::{RangingRole, SetRangingRoleCommand};
+use crate::commands::set_rf_frequency::SetRFFrequencyCommand;
+use crate::commands::set_rx::{RxPeriod, SetRxModeCommand};
+use crate::commands::set_rx_dc::SetRxDCModeCommand;
+use crate::commands::set_sleep::SetSleepModeCommand;
+use crate::commands::set_standby::{SetStandbyModeCommand, StandbyMode};
+use crate::commands::set_tx::{SetTxModeCommand, TxPeriod};
+use crate::commands::set_tx_continuous_wave::SetTXContinuousWaveCommand;
+use crate::commands::set_tx_long_preamble::SetTXLongPreambleCommand;
+use crate::commands::set_tx_parameters::{SetTxParametersCommand, TxRampTime};
+
+fn frame<MODE: SX1280Mode, C: SX1280Command<MODE>>(command: C) -> Vec<u8> {
+    let mut frame = std::vec![C::OPCODE];
+    frame.extend_from_slice(command.as_write_bytes().ok().unwrap().as_ref());
+    frame
+}
+
+fn decode<MODE: SX1280Mode, C: SX1280Command<MODE>>(_command: C, status: u8, payload: &[u8]) -> C::ResponseType {
+    let mut buffer = C::ResponseBufferType::default();
+    buffer.as_mut().copy_from_slice(payload);
+    (Status::from_bits(status), buffer).try_into().ok().unwrap()
+}
+
+/// Datasheet command name, frame built by the driver and expected frame
+fn cases() -> Vec<(&'static str, Vec<u8>, &'static [u8])> {
+    let cases = std::vec![
+        ("GetStatus", frame::<ModeLoRa, _>(GetStatusCommand), &[0xC0][..]),
+        ("SetSleep", frame::<ModeLoRa, _>(SetSleepModeCommand::new().with_retain_ram(true)), &[0x84, 0x01]),
+        ("SetStandby", frame::<ModeLoRa, _>(SetStandbyModeCommand { mode: StandbyMode::StandbyXOSC }), &[0x80, 0x01]),
+        ("SetFs", frame::<ModeLoRa, _>(SetFrequencySynthesisModeCommand), &[0xC1]),
+        ("SetTx", frame::<ModeLoRa, _>(SetTxModeCommand { period: TxPeriod::Interval(500), period_base: PeriodBase::Base1ms }), &[0x83, 0x02, 0x01, 0xF4]),
+        ("SetRx", frame::<ModeLoRa, _>(SetRxModeCommand { period: RxPeriod::Infinite, period_base: PeriodBase::Base15u625s }), &[0x82, 0x00, 0xFF, 0xFF]),
+        ("SetRx", frame::<ModeGFSK, _>(SetRxModeCommand { period: RxPeriod::Timeout(0x1234), period_base: PeriodBase::Base4ms }), &[0x82, 0x03, 0x12, 0x34]),
+        ("SetRxDutyCycle", frame::<ModeLoRa, _>(SetRxDCModeCommand { sleep_period: 0x0100, rx_period: 0x0040, period_base: PeriodBase::Base62u5s }), &[0x94, 0x01, 0x00, 0x40, 0x01, 0x00]),
+        ("SetLongPreamble", frame::<ModeLoRa, _>(SetLongPreambleModeCommand(true)), &[0x9B, 0x01]),
+        ("SetLongPreamble", frame::<ModeGFSK, _>(SetLongPreambleModeCommand(false)), &[0x9B, 0x00]),
+        ("SetCad", frame::<ModeLoRa, _>(SetCAD), &[0xC5]),
+        ("SetTxContinuousWave", frame::<ModeLoRa, _>(SetTXContinuousWaveCommand), &[0xD1]),
+        ("SetTxContinuousPreamble", frame::<ModeLoRa, _>(SetTXLongPreambleCommand), &[0xD2]),
+        ("SetPacketType", frame::<ModeLoRa, _>(SetPacketTypeCommand(PacketType::LoRa)), &[0x8A, 0x01]),
+        ("SetPacketType", frame::<ModeLoRa, _>(SetPacketTypeCommand(PacketType::BLE)), &[0x8A, 0x04]),
+        ("GetPacketType", frame::<ModeLoRa, _>(GetPacketTypeCommand), &[0x03]),
+        ("SetRfFrequency", frame::<ModeLoRa, _>(SetRFFrequencyCommand(2_400_000_000u32)), &[0x86, 0xB8, 0x9D, 0x89]),
+        ("SetTxParams", frame::<ModeLoRa, _>(SetTxParametersCommand { power: 13, ramp: TxRampTime::Ramp20us }), &[0x8E, 0x1F, 0xE0]),
+        ("SetTxParams", frame::<ModeFLRC, _>(SetTxParametersCommand { power: -18, ramp: TxRampTime::Ramp2us }), &[0x8E, 0x00, 0x00]),
+        ("SetCadParams", frame::<ModeLoRa, _>(SetCadParametersCommand { symbol_number: CadSymbolsNumber::Cad8Symbols }), &[0x88, 0x60]),
+        ("SetBufferBaseAddress", frame::<ModeLoRa, _>(SetBufferBaseAddressCommand { tx_base_address: 0x80, rx_base_address: 0x00 }), &[0x8F, 0x80, 0x00]),
+        ("SetModulationParams", frame::<ModeLoRa, _>(SetLoraModulationParameters {
+            spreading_factor: SpreadingFactor::SF7,
+            bandwidth: Bandwidth::BW1M625Hz,
+            coding_rate: CodingRate::CR4_5,
+        }), &[0x8B, 0x70, 0x0A, 0x01]),
+        ("SetModulationParams", frame::<ModeGFSK, _>(SetGfskModulationParameters {
+            bitrate_bandwidth: GfskBitrateBandwidth::BR2M0_BW2M4,
+            modulation_index: GfskModulationIndex::MI0_5,
+            modulation_shaping: ModulationShaping::BT0_5,
+        }), &[0x8B, 0x04, 0x01, 0x20]),
+        ("SetModulationParams", frame::<ModeFLRC, _>(SetFlrcModulationParameters {
+            bitrate_bandwidth: FlrcBitrateBandwidth::BR1M3_BW1M2,
+            coding_rate: FlrcCodingRate::CR3_4,
+            modulation_shaping: ModulationShaping::BTOff,
+        }), &[0x8B, 0x45, 0x02, 0x00]),
+        ("SetModulationParams", frame::<ModeBLE, _>(SetBleModulationParameters::default()), &[0x8B, 0x45, 0x01, 0x20]),
+        ("SetPacketParams", frame::<ModeLoRa, _>(SetLoraPacketParameters {
+            preamble_length: LoRaPreambleLength::try_from(12).ok().unwrap(),
+            header_type: LoRaHeaderType::Explicit,
+            payload_length: 64,
+            crc_mode: LoRaCrcMode::Enabled,
+            iq_mode: LoRaIQMode::Standard,
+        }), &[0x8C, 0x23, 0x00, 0x40, 0x20, 0x40, 0x00, 0x00]),
+        ("SetPacketParams", frame::<ModeGFSK, _>(SetGfskPacketParameters {
+            preamble_length: PreambleLength::Bits16,
+            sync_word_length: GfskSyncWordLength::Bytes4,
+            sync_word_match: SyncWordMatch::SyncWord1,
+            header_type: PacketLengthMode::VariableLength,
+            payload_length: 255,
+            crc_length: GfskCrcLength::Bytes2,
+            whitening: Whitening::Disabled,
+        }), &[0x8C, 0x30, 0x06, 0x10, 0x20, 0xFF, 0x20, 0x08]),
+        ("SetPacketParams", frame::<ModeFLRC, _>(SetFlrcPacketParameters {
+            agc_preamble_length: PreambleLength::Bits32,
+            sync_word_length: FlrcSyncWordLength::Bytes4,
+            sync_word_match: SyncWordMatch::SyncWord1Or2,
+            header_type: PacketLengthMode::FixedLength,
+            payload_length: 127,
+            crc_length: FlrcCrcLength::Bytes3,
+            whitening: Whitening::Disabled,
+        }), &[0x8C, 0x70, 0x04, 0x30, 0x00, 0x7F, 0x20, 0x08]),
+        ("SetPacketParams", frame::<ModeBLE, _>(SetBlePacketParameters {
+            connection_state: BleConnectionState::MaxPayload37Bytes,
+            crc_field: BleCrcField::Bytes3,
+            packet_type: BleTestPacketType::Prbs9,
+            whitening: Whitening::Enabled,
+        }), &[0x8C, 0x20, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00]),
+        ("GetRxBufferStatus", frame::<ModeLoRa, _>(GetRxBufferStatusCommand), &[0x17]),
+        ("GetPacketStatus", frame::<ModeLoRa, _>(GetPacketStatusCommand), &[0x1D]),
+        ("GetPacketStatus", frame::<ModeGFSK, _>(GetPacketStatusCommand), &[0x1D]),
+        ("GetRssiInst", frame::<ModeLoRa, _>(GetInstantaneousRssiCommand), &[0x1F]),
+        ("SetDioIrqParams", frame::<ModeLoRa, _>(SetIRQParametersCommand {
+            irq_mask: SX1280Interrupt::TxDone | SX1280Interrupt::RxDone | SX1280Interrupt::RXTXTimeout,
+            dio_mask: [SX1280Interrupt::TxDone | SX1280Interrupt::RxDone, SX1280Interrupt::RXTXTimeout, SX1280Interrupt::empty()],
+        }), &[0x8D, 0x40, 0x03, 0x00, 0x03, 0x40, 0x00, 0x00, 0x00]),
+        ("GetIrqStatus", frame::<ModeLoRa, _>(GetIrqStatusCommand), &[0x15]),
+        ("ClrIrqStatus", frame::<ModeLoRa, _>(ClearIrqCommand(SX1280Interrupt::all())), &[0x97, 0xFF, 0xFF]),
+        ("SetAutoTx", frame::<ModeLoRa, _>(SetAutoTxCommand(92)), &[0x98, 0x00, 0x5C]),
+        ("SetAutoFs", frame::<ModeLoRa, _>(SetAutoFsCommand(true)), &[0x9E, 0x01]),
+    ];
+    #[cfg(feature = "ranging")]
+    let cases = [cases, std::vec![
+        ("SetModulationParams", frame::<ModeLoRaRanging, _>(SetLoraModulationParameters {
+            spreading_factor: SpreadingFactor::SF10,
+            bandwidth: Bandwidth::BW812k5Hz,
+            coding_rate: CodingRate::CR4_8,
+        }), &[0x8B, 0xA0, 0x18, 0x04][..]),
+        ("SetRangingRole", frame::<ModeLoRaRanging, _>(SetRangingRoleCommand(RangingRole::Master)), &[0xA3, 0x01]),
+    ]].concat();
+    cases
+}
+
+#[test]
+fn commands_match_the_datasheet() {
+    for (name, frame, golden) in cases() {
+        assert_eq!(frame.as_slice(), golden, "{}", name);
+    }
+}
+
+#[test]
+fn opcodes_are_not_shared() {
+    let cases = cases();
+    for (name, frame, _) in &cases {
+        for (other, other_frame, _) in &cases {
+            assert!(name == other || frame[0] != other_frame[0], "{} and {} share opcode {:#04X}", name, other, frame[0]);
+        }
+    }
+}
+
+#[test]
+fn responses_match_the_datasheet() {
+    // circuit mode STDBY_RC, command status timeout, then data available for the commands returning data
+    let status = decode::<ModeLoRa, _>(GetStatusCommand, 0x4C, &[]);
+    assert_eq!(status.circuit_mode(), CircuitMode::StandbyRC);
+    assert_eq!(status.command_status(), CommandStatus::CommandTimeout);
+
+    let packet_type = decode::<ModeLoRa, _>(GetPacketTypeCommand, 0x48, &[0x03]);
+    assert_eq!(u8::from(packet_type), PacketType::FLRC as u8);
+
+    let buffer = decode::<ModeLoRa, _>(GetRxBufferStatusCommand, 0x48, &[0x40, 0x80]);
+    assert_eq!(buffer.rx_payload_len, 0x40);
+    assert_eq!(buffer.rx_buffer_start_pointer, 0x80);
+
+    let rssi = decode::<ModeLoRa, _>(GetInstantaneousRssiCommand, 0x48, &[0xA0]);
+    assert_eq!(*rssi, -80.0);
+
+    let irq = decode::<ModeLoRa, _>(GetIrqStatusCommand, 0x48, &[0x40, 0x02]);
+    assert_eq!(irq.bits(), (SX1280Interrupt::RXTXTimeout | SX1280Interrupt::RxDone).bits());
+
+    // 5 dB SNR, then -4 dB which lowers the packet RSSI below the sync RSSI
+    let lora = decode::<ModeLoRa, _>(GetPacketStatusCommand, 0x48, &[0x80, 0x14, 0x00, 0x00, 0x00]);
+    assert_eq!((lora.rssi, lora.snr), (-64.0, 5.0));
+    let lora = decode::<ModeLoRa, _>(GetPacketStatusCommand, 0x48, &[0x80, 0xF0, 0x00, 0x00, 0x00]);
+    assert_eq!((lora.rssi, lora.snr), (-68.0, -4.0));
+
+    let gfsk = decode::<ModeGFSK, _>(GetPacketStatusCommand, 0x48, &[0x00, 0x90, 0x12, 0x01, 0x02]);
+    assert_eq!(gfsk.rssi_sync, -72.0);
+    assert_eq!(gfsk.errors.bits(), (PacketErrors::CrcError | PacketErrors::PacketReceived).bits());
+    assert_eq!(gfsk.status.bits(), PacketStatusFlags::PacketSent.bits());
+    assert_eq!(gfsk.sync_address, 2);
+}
@@ -1,6 +1,7 @@
 use core::ops::Deref;
-use crate::sx1280::commands::{NullArgumentsBufferType, SX1280Command, SX1280CommandError};
-use crate::sx1280::lora::ModeLoRa;
+use crate::commands::{NullArgumentsBufferType, SX1280Command, SX1280CommandError};
+use crate::commands::get_status::Status;
+use crate::lora::ModeLoRa;
 
 pub struct GetInstantaneousRssiCommand;
 
@@ -17,7 +18,7 @@ impl Deref for InstantaneousRssi {
 impl SX1280Command<ModeLoRa> for GetInstantaneousRssiCommand {
     const OPCODE: u8 = 0x1F;
     type ArgumentsBufferType = NullArgumentsBufferType;
-    type ResponseBufferType = [u8; 2];
+    type ResponseBufferType = [u8; 1];
     type ResponseType = InstantaneousRssi;
 
     fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
@@ -25,9 +26,9 @@ impl SX1280Command<ModeLoRa> for GetInstantaneousRssiCommand {
     }
 }
 
-impl TryFrom<(u8, [u8; 2])> for InstantaneousRssi {
+impl TryFrom<(Status, [u8; 1])> for InstantaneousRssi {
     type Error = SX1280CommandError;
-    fn try_from(value: (u8, [u8; 2])) -> Result<Self, Self::Error> {
-        Ok(Self(-(value.0 as f32) / 2.0f32))
+    fn try_from(value: (Status, [u8; 1])) -> Result<Self, Self::Error> {
+        Ok(Self(-(value.1[0] as f32) / 2.0f32))
     }
 }
\ No newline at end of file
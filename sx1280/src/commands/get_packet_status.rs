@@ -0,0 +1,141 @@
+use bitflags::bitflags;
+use crate::commands::{NullArgumentsBufferType, SX1280Command, SX1280CommandError};
+use crate::commands::get_status::Status;
+use crate::ble::ModeBLE;
+use crate::flrc::ModeFLRC;
+use crate::gfsk::ModeGFSK;
+use crate::lora::ModeLoRa;
+#[cfg(feature = "ranging")]
+use crate::ranging::ModeLoRaRanging;
+
+pub struct GetPacketStatusCommand;
+
+pub struct LoRaPacketStatus {
+    pub rssi: f32,
+    pub snr: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PacketErrors(u8);
+
+bitflags! {
+    impl PacketErrors: u8 {
+        const PacketControllerBusy =    0x01;
+        const PacketReceived =          0x02;
+        const HeaderReceived =          0x04;
+        const AbortError =              0x08;
+        const CrcError =                0x10;
+        const LengthError =             0x20;
+        const SyncError =               0x40;
+        const _ = !0;
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PacketStatusFlags(u8);
+
+bitflags! {
+    impl PacketStatusFlags: u8 {
+        const PacketSent =  0x01;
+        const RxNoAck =     0x20;
+        const _ = !0;
+    }
+}
+
+pub struct PacketStatus {
+    pub rssi_sync: f32,
+    pub errors: PacketErrors,
+    pub status: PacketStatusFlags,
+    pub sync_address: u8,
+}
+
+impl SX1280Command<ModeLoRa> for GetPacketStatusCommand {
+    const OPCODE: u8 = 0x1D;
+    type ArgumentsBufferType = NullArgumentsBufferType;
+    type ResponseBufferType = [u8; 5];
+    type ResponseType = LoRaPacketStatus;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([0; 0])
+    }
+}
+
+#[cfg(feature = "ranging")]
+impl SX1280Command<ModeLoRaRanging> for GetPacketStatusCommand {
+    const OPCODE: u8 = 0x1D;
+    type ArgumentsBufferType = NullArgumentsBufferType;
+    type ResponseBufferType = [u8; 5];
+    type ResponseType = LoRaPacketStatus;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([0; 0])
+    }
+}
+
+impl SX1280Command<ModeGFSK> for GetPacketStatusCommand {
+    const OPCODE: u8 = 0x1D;
+    type ArgumentsBufferType = NullArgumentsBufferType;
+    type ResponseBufferType = [u8; 5];
+    type ResponseType = PacketStatus;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([0; 0])
+    }
+}
+
+impl SX1280Command<ModeFLRC> for GetPacketStatusCommand {
+    const OPCODE: u8 = 0x1D;
+    type ArgumentsBufferType = NullArgumentsBufferType;
+    type ResponseBufferType = [u8; 5];
+    type ResponseType = PacketStatus;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([0; 0])
+    }
+}
+
+impl SX1280Command<ModeBLE> for GetPacketStatusCommand {
+    const OPCODE: u8 = 0x1D;
+    type ArgumentsBufferType = NullArgumentsBufferType;
+    type ResponseBufferType = [u8; 5];
+    type ResponseType = PacketStatus;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([0; 0])
+    }
+}
+
+impl TryFrom<(Status, [u8; 5])> for LoRaPacketStatus {
+    type Error = SX1280CommandError;
+
+    fn try_from(value: (Status, [u8; 5])) -> Result<Self, Self::Error> {
+        let rssi = -(value.1[0] as f32) / 2.0f32;
+        let snr = (value.1[1] as i8) as f32 / 4.0f32;
+        if snr <= 0.0 {
+            Ok(Self{
+                rssi: rssi + snr,
+                snr
+            })
+        } else {
+            Ok(Self{
+                rssi,
+                snr
+            })
+        }
+    }
+}
+
+impl TryFrom<(Status, [u8; 5])> for PacketStatus {
+    type Error = SX1280CommandError;
+
+    fn try_from(value: (Status, [u8; 5])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            rssi_sync: -(value.1[1] as f32) / 2.0f32,
+            errors: PacketErrors::from_bits_retain(value.1[2]),
+            status: PacketStatusFlags::from_bits_retain(value.1[3]),
+            sync_address: value.1[4] & 0x07,
+        })
+    }
+}
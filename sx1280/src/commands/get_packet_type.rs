@@ -0,0 +1,25 @@
+use crate::commands::{NullArgumentsBufferType, SX1280Command, SX1280CommandError};
+use crate::commands::get_status::Status;
+use crate::commands::set_packet_type::PacketType;
+use crate::SX1280Mode;
+
+pub struct GetPacketTypeCommand;
+
+impl<MODE: SX1280Mode> SX1280Command<MODE> for GetPacketTypeCommand {
+    const OPCODE: u8 = 0x03;
+    type ArgumentsBufferType = NullArgumentsBufferType;
+    type ResponseBufferType = [u8; 1];
+    type ResponseType = PacketType;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([0; 0])
+    }
+}
+
+impl TryFrom<(Status, [u8; 1])> for PacketType {
+    type Error = SX1280CommandError;
+
+    fn try_from(value: (Status, [u8; 1])) -> Result<Self, Self::Error> {
+        PacketType::try_from(value.1[0]).map_err(|_| SX1280CommandError::InvalidResponse)
+    }
+}
@@ -1,6 +1,7 @@
 use bitfield_struct::bitfield;
-use crate::sx1280::commands::{NullArgumentsBufferType, SX1280Command, SX1280CommandError};
-use crate::sx1280::SX1280Mode;
+use crate::commands::{NullArgumentsBufferType, SX1280Command, SX1280CommandError};
+use crate::SX1280Mode;
+use crate::commands::get_status::Status;
 
 pub struct GetRxBufferStatusCommand;
 
@@ -12,7 +13,7 @@ pub struct BufferStatus {
 impl<MODE: SX1280Mode> SX1280Command<MODE> for GetRxBufferStatusCommand {
     const OPCODE: u8 = 0x17;
     type ArgumentsBufferType = NullArgumentsBufferType;
-    type ResponseBufferType = [u8; 3];
+    type ResponseBufferType = [u8; 2];
     type ResponseType = BufferStatus;
 
     fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
@@ -20,13 +21,13 @@ impl<MODE: SX1280Mode> SX1280Command<MODE> for GetRxBufferStatusCommand {
     }
 }
 
-impl TryFrom<(u8, [u8; 3])> for BufferStatus {
+impl TryFrom<(Status, [u8; 2])> for BufferStatus {
     type Error = SX1280CommandError;
 
-    fn try_from(value: (u8, [u8; 3])) -> Result<Self, Self::Error> {
+    fn try_from(value: (Status, [u8; 2])) -> Result<Self, Self::Error> {
         Ok(Self {
-            rx_buffer_start_pointer: value.1[2],
-            rx_payload_len: value.1[1],
+            rx_buffer_start_pointer: value.1[1],
+            rx_payload_len: value.1[0],
         })
     }
 }
\ No newline at end of file
@@ -1,13 +1,13 @@
-use defmt::Format;
 use bitfield_struct::{bitfield, FromBits, IntoBits};
 use num_enum::FromPrimitive;
 use num_enum_derive::IntoPrimitive;
-use crate::sx1280::commands::{NullArgumentsBufferType, NullResponseBufferType, SX1280Command, SX1280CommandError};
-use crate::sx1280::SX1280Mode;
+use crate::commands::{NullArgumentsBufferType, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::SX1280Mode;
 
 pub struct GetStatusCommand;
 
-#[derive(Debug, Format, FromPrimitive, IntoPrimitive, IntoBits, FromBits)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, IntoPrimitive, IntoBits, FromBits)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum CommandStatus {
     CommandOk = 1,
@@ -20,7 +20,8 @@ pub enum CommandStatus {
     Unknown,
 }
 
-#[derive(Debug, Format, FromPrimitive, IntoPrimitive, IntoBits, FromBits)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, IntoPrimitive, IntoBits, FromBits)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum CircuitMode {
     StandbyRC = 2,
@@ -32,7 +33,7 @@ pub enum CircuitMode {
     Unknown,
 }
 
-#[bitfield(u8, defmt=true)]
+#[bitfield(u8, defmt = cfg(feature = "defmt"))]
 pub struct Status {
     #[bits(2)]
     _pad: u8,
@@ -53,10 +54,10 @@ impl<MODE: SX1280Mode> SX1280Command<MODE> for GetStatusCommand {
     }
 }
 
-impl TryFrom<(u8, [u8; 0])> for Status {
+impl TryFrom<(Status, [u8; 0])> for Status {
     type Error = SX1280CommandError;
 
-    fn try_from(value: (u8, [u8; 0])) -> Result<Self, Self::Error> {
-        Ok(Self(value.0))
+    fn try_from(value: (Status, [u8; 0])) -> Result<Self, Self::Error> {
+        Ok(value.0)
     }
 }
\ No newline at end of file
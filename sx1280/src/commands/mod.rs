@@ -0,0 +1,190 @@
+pub mod get_status;
+pub mod set_packet_type;
+pub mod get_packet_type;
+pub mod set_sleep;
+pub mod set_standby;
+pub mod set_fs;
+pub mod set_tx;
+pub mod set_rx;
+pub mod set_rx_dc;
+pub mod set_long_preamble;
+pub mod set_cad;
+pub mod set_rf_frequency;
+pub mod set_tx_parameters;
+pub mod set_cad_parameters;
+pub mod set_buffer_base_address;
+pub mod set_modulation_parameters;
+pub mod set_packet_parameters;
+pub mod get_rx_buffer_status;
+pub mod get_packet_status;
+pub mod set_tx_continuous_wave;
+pub mod set_tx_long_preamble;
+pub mod get_instantaneous_rssi;
+pub mod set_irq_params;
+pub mod get_irq_status;
+pub mod clear_irq;
+pub mod set_auto_tx;
+pub mod set_auto_fs;
+#[cfg(feature = "ranging")]
+pub mod set_ranging_role;
+#[cfg(test)]
+mod conformance;
+
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+use bitfield_struct::{FromBits, IntoBits};
+use bitflags::bitflags;
+use fugit::Duration;
+use num_enum_derive::{FromPrimitive, IntoPrimitive, TryFromPrimitive};
+use crate::SX1280Mode;
+use crate::commands::get_status::Status;
+use crate::state::RadioState;
+
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SX1280CommandError{
+    InvalidResponse,
+    InvalidArgument,
+    Other
+}
+
+impl Display for SX1280CommandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Command Error")
+    }
+}
+
+impl Error for SX1280CommandError {}
+
+pub trait SX1280Command<MODE: SX1280Mode> {
+    const OPCODE: u8;
+
+    type ArgumentsBufferType: AsRef<[u8]> + AsMut<[u8]>;
+    /// Bytes returned after the status byte, empty for commands that only return a status
+    type ResponseBufferType: AsRef<[u8]> + AsMut<[u8]> + Default;
+
+    type ResponseType: Sized + TryFrom<(Status, Self::ResponseBufferType), Error = SX1280CommandError>;
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError>;
+
+    /// State the radio enters once the command is sent, if it changes it
+    fn next_state(&self) -> Option<RadioState> {
+        None
+    }
+}
+
+
+pub struct NullResponse;
+pub type NullResponseBufferType = [u8; 0];
+pub type NullArgumentsBufferType = [u8; 0];
+impl TryFrom<(Status, [u8; 0])> for NullResponse {
+    type Error = SX1280CommandError;
+
+    fn try_from(_value: (Status, [u8; 0])) -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum PeriodBase {
+    Base15u625s = 0,
+    Base62u5s = 1,
+    Base1ms = 2,
+    Base4ms = 3,
+}
+
+impl PeriodBase {
+    pub const ALL: [PeriodBase; 4] = [PeriodBase::Base15u625s, PeriodBase::Base62u5s, PeriodBase::Base1ms, PeriodBase::Base4ms];
+
+    /// Length of one period step in ns
+    pub fn step_ns(&self) -> u64 {
+        match self {
+            PeriodBase::Base15u625s => 15_625,
+            PeriodBase::Base62u5s => 62_500,
+            PeriodBase::Base1ms => 1_000_000,
+            PeriodBase::Base4ms => 4_000_000,
+        }
+    }
+
+    /// Finest base in which `ns` takes at most `max_steps` steps
+    pub fn fitting(ns: u64, max_steps: u16) -> Option<PeriodBase> {
+        PeriodBase::ALL.into_iter().find(|base| base.steps(ns) <= max_steps as u64)
+    }
+
+    /// Finest base able to count `duration` in at most `max_steps` steps, with the step count.
+    /// A zero duration is refused, it has a special meaning in every command taking a period.
+    pub fn for_duration<T, const NOM: u32, const DENOM: u32>(duration: Duration<T, NOM, DENOM>, max_steps: u16) -> Result<(PeriodBase, u16), SX1280CommandError>
+    where
+        Duration<T, NOM, DENOM>: Into<Duration<u64, NOM, DENOM>>,
+    {
+        let duration: Duration<u64, NOM, DENOM> = duration.into();
+        let ns = duration.to_nanos();
+        if ns == 0 {
+            return Err(SX1280CommandError::InvalidArgument)
+        }
+        let base = PeriodBase::fitting(ns, max_steps).ok_or(SX1280CommandError::InvalidArgument)?;
+        Ok((base, base.steps(ns) as u16))
+    }
+
+    /// Number of steps covering `ns`, rounded up
+    pub fn steps(&self, ns: u64) -> u64 {
+        ns.div_ceil(self.step_ns())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SX1280Interrupt(u16);
+
+bitflags! {
+    impl SX1280Interrupt: u16 {
+        const TxDone =                      0x0001;
+        const RxDone =                      0x0002;
+        const SyncWordValid =               0x0004;
+        const SyncWordError =               0x0008;
+        const HeaderValid =                 0x0010;
+        const HeaderError =                 0x0020;
+        const CRCError =                    0x0040;
+        const RangingSlaveResponseDone =    0x0080;
+        const RangingSlaveRequestDiscard =  0x0100;
+        const RangingMasterResultValid =    0x0200;
+        const RangingMasterTimeout =        0x0400;
+        const RangingSlaveRequestValid =    0x0800;
+        const CADDone =                     0x1000;
+        const CADDetect =                   0x2000;
+        const RXTXTimeout =                 0x4000;
+        const PreambleDetect =              0x8000;
+        const _ = !0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::set_rx::{RxPeriod, SetRxModeCommand};
+    use crate::commands::set_tx::{SetTxModeCommand, TxPeriod};
+    use crate::lora::ModeLoRa;
+
+    #[test]
+    fn periods_from_durations_pick_finest_base() {
+        use fugit::ExtU64;
+        let command = SetTxModeCommand::with_timeout(100.millis()).ok().unwrap();
+        assert!(matches!((command.period, command.period_base), (TxPeriod::Interval(6400), PeriodBase::Base15u625s)));
+        let command = SetTxModeCommand::with_timeout(2.secs()).ok().unwrap();
+        assert!(matches!((command.period, command.period_base), (TxPeriod::Interval(32_000), PeriodBase::Base62u5s)));
+        assert!(SetTxModeCommand::with_timeout(300.secs()).is_err());
+        assert!(SetTxModeCommand::with_timeout(0.millis()).is_err());
+        let command = SetTxModeCommand::with_timeout(fugit::MillisDurationU32::from_ticks(100)).ok().unwrap();
+        assert!(matches!((command.period, command.period_base), (TxPeriod::Interval(6400), PeriodBase::Base15u625s)));
+
+        // a full 16 bit count would mean continuous reception
+        let command = SetRxModeCommand::with_timeout(fugit::NanosDurationU64::from_ticks(1_023_984_375)).ok().unwrap();
+        assert!(matches!((command.period, command.period_base), (RxPeriod::Timeout(16_384), PeriodBase::Base62u5s)));
+
+        let command = SetRxModeCommand { period: RxPeriod::Timeout(70_000), period_base: PeriodBase::Base1ms };
+        assert!(<SetRxModeCommand as SX1280Command<ModeLoRa>>::as_write_bytes(&command).is_err());
+    }
+}
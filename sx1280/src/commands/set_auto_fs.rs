@@ -0,0 +1,18 @@
+use crate::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::SX1280ModeValid;
+
+/// Returns to frequency synthesis instead of standby after TX or RX.
+/// The SX1280 has no automatic TX to RX switch, this is what shortens the turnaround
+/// when RX is started from the MCU right after a transmission.
+pub struct SetAutoFsCommand(pub bool);
+
+impl<MODE: SX1280ModeValid> SX1280Command<MODE> for SetAutoFsCommand {
+    const OPCODE: u8 = 0x9E;
+    type ArgumentsBufferType = [u8; 1];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([if self.0 { 1 } else { 0 }])
+    }
+}
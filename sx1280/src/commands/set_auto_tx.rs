@@ -0,0 +1,17 @@
+use crate::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::SX1280ModeValid;
+
+/// Switches the radio to TX the given number of µs (plus a fixed 33 µs offset) after every RxDone,
+/// sending what is in the buffer at the TX base address. `SetAutoTxCommand(0)` disables it.
+pub struct SetAutoTxCommand(pub u16);
+
+impl<MODE: SX1280ModeValid> SX1280Command<MODE> for SetAutoTxCommand {
+    const OPCODE: u8 = 0x98;
+    type ArgumentsBufferType = [u8; 2];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok(self.0.to_be_bytes())
+    }
+}
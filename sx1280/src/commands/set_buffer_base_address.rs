@@ -1,6 +1,6 @@
 use bitfield_struct::bitfield;
-use crate::sx1280::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
-use crate::sx1280::SX1280ModeValid;
+use crate::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::SX1280ModeValid;
 
 pub struct SetBufferBaseAddressCommand{
     pub rx_base_address: u8,
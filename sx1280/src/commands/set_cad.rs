@@ -1,5 +1,6 @@
-use crate::sx1280::commands::{NullArgumentsBufferType, NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
-use crate::sx1280::lora::ModeLoRa;
+use crate::commands::{NullArgumentsBufferType, NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::lora::ModeLoRa;
+use crate::state::RadioState;
 
 pub struct SetCAD;
 
@@ -12,4 +13,8 @@ impl SX1280Command<ModeLoRa> for SetCAD {
     fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
         Ok([])
     }
+
+    fn next_state(&self) -> Option<RadioState> {
+        Some(RadioState::Cad)
+    }
 }
@@ -1,10 +1,10 @@
 use bitfield_struct::{bitfield, FromBits, IntoBits};
-use defmt::Format;
 use num_enum_derive::{FromPrimitive, IntoPrimitive, TryFromPrimitive};
-use crate::sx1280::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
-use crate::sx1280::lora::ModeLoRa;
+use crate::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::lora::ModeLoRa;
 
-#[derive(Clone, Copy, Debug, Format, TryFromPrimitive)]
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum CadSymbolsNumber {
     Cad1Symbol = 0,
@@ -1,5 +1,6 @@
-use crate::sx1280::commands::{NullArgumentsBufferType, NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
-use crate::sx1280::SX1280ModeValid;
+use crate::commands::{NullArgumentsBufferType, NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::SX1280ModeValid;
+use crate::state::RadioState;
 
 pub struct SetFrequencySynthesisModeCommand;
 
@@ -12,4 +13,8 @@ impl<MODE: SX1280ModeValid> SX1280Command<MODE> for SetFrequencySynthesisModeCom
     fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
         Ok([0; 0])
     }
+
+    fn next_state(&self) -> Option<RadioState> {
+        Some(RadioState::FrequencySynthesis)
+    }
 }
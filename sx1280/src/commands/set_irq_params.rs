@@ -1,8 +1,7 @@
 use bitfield_struct::{bitfield, FromBits, IntoBits};
-use defmt::Format;
 use num_enum_derive::{FromPrimitive, IntoPrimitive};
-use crate::sx1280::commands::{NullResponse, NullResponseBufferType, PeriodBase, SX1280Command, SX1280CommandError, SX1280Interrupt};
-use crate::sx1280::SX1280ModeValid;
+use crate::commands::{NullResponse, NullResponseBufferType, PeriodBase, SX1280Command, SX1280CommandError, SX1280Interrupt};
+use crate::SX1280ModeValid;
 
 pub struct SetIRQParametersCommand {
     pub irq_mask: SX1280Interrupt,
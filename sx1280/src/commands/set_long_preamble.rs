@@ -1,7 +1,7 @@
 use bitfield_struct::{bitfield};
-use crate::sx1280::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
-use crate::sx1280::gfsk::ModeGFSK;
-use crate::sx1280::lora::ModeLoRa;
+use crate::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::gfsk::ModeGFSK;
+use crate::lora::ModeLoRa;
 
 pub struct SetLongPreambleModeCommand(pub bool);
 
@@ -0,0 +1,219 @@
+use bitfield_struct::{bitfield, FromBits, IntoBits};
+use num_enum_derive::{FromPrimitive, IntoPrimitive, TryFromPrimitive};
+use crate::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::ble::ModeBLE;
+use crate::flrc::ModeFLRC;
+use crate::gfsk::ModeGFSK;
+use crate::lora::ModeLoRa;
+#[cfg(feature = "ranging")]
+use crate::ranging::ModeLoRaRanging;
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum SpreadingFactor {
+    SF5 = 0x50,
+    SF6 = 0x60,
+    SF7 = 0x70,
+    SF8 = 0x80,
+    SF9 = 0x90,
+    SF10 = 0xA0,
+    SF11 = 0xB0,
+    SF12 = 0xC0,
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum Bandwidth {
+    BW1M625Hz = 0x0A,
+    BW812k5Hz = 0x18,
+    BW406k25Hz = 0x26,
+    BW203k125Hz = 0x34,
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum CodingRate {
+    CR4_5 = 1,
+    CR4_6 = 2,
+    CR4_7 = 3,
+    CR4_8 = 4,
+    CR4_5Alt = 5,
+    CR4_6Alt = 6,
+    CR4_8Alt = 7,
+}
+
+impl Bandwidth {
+    pub fn hz(&self) -> u32 {
+        match self {
+            Bandwidth::BW1M625Hz => 1_625_000,
+            Bandwidth::BW812k5Hz => 812_500,
+            Bandwidth::BW406k25Hz => 406_250,
+            Bandwidth::BW203k125Hz => 203_125,
+        }
+    }
+}
+
+pub struct SetLoraModulationParameters{
+    pub coding_rate: CodingRate,
+    pub bandwidth: Bandwidth,
+    pub spreading_factor: SpreadingFactor,
+}
+
+impl SX1280Command<ModeLoRa> for SetLoraModulationParameters {
+    const OPCODE: u8 = 0x8B;
+    type ArgumentsBufferType = [u8; 3];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([self.spreading_factor as u8, self.bandwidth as u8, self.coding_rate as u8])
+    }
+}
+
+#[cfg(feature = "ranging")]
+impl SX1280Command<ModeLoRaRanging> for SetLoraModulationParameters {
+    const OPCODE: u8 = 0x8B;
+    type ArgumentsBufferType = [u8; 3];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([self.spreading_factor as u8, self.bandwidth as u8, self.coding_rate as u8])
+    }
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum GfskBitrateBandwidth {
+    BR2M0_BW2M4 = 0x04,
+    BR1M6_BW2M4 = 0x28,
+    BR1M0_BW2M4 = 0x4C,
+    BR1M0_BW1M2 = 0x45,
+    BR0M8_BW2M4 = 0x70,
+    BR0M8_BW1M2 = 0x69,
+    BR0M5_BW1M2 = 0x8D,
+    BR0M5_BW0M6 = 0x86,
+    BR0M4_BW1M2 = 0xB1,
+    BR0M4_BW0M6 = 0xAA,
+    BR0M25_BW0M6 = 0xCE,
+    BR0M25_BW0M3 = 0xC7,
+    BR0M125_BW0M3 = 0xEF,
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum GfskModulationIndex {
+    MI0_35 = 0x00,
+    MI0_5 = 0x01,
+    MI0_75 = 0x02,
+    MI1_0 = 0x03,
+    MI1_25 = 0x04,
+    MI1_5 = 0x05,
+    MI1_75 = 0x06,
+    MI2_0 = 0x07,
+    MI2_25 = 0x08,
+    MI2_5 = 0x09,
+    MI2_75 = 0x0A,
+    MI3_0 = 0x0B,
+    MI3_25 = 0x0C,
+    MI3_5 = 0x0D,
+    MI3_75 = 0x0E,
+    MI4_0 = 0x0F,
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum ModulationShaping {
+    BTOff = 0x00,
+    BT1_0 = 0x10,
+    BT0_5 = 0x20,
+}
+
+pub struct SetGfskModulationParameters{
+    pub bitrate_bandwidth: GfskBitrateBandwidth,
+    pub modulation_index: GfskModulationIndex,
+    pub modulation_shaping: ModulationShaping,
+}
+
+impl SX1280Command<ModeGFSK> for SetGfskModulationParameters {
+    const OPCODE: u8 = 0x8B;
+    type ArgumentsBufferType = [u8; 3];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([self.bitrate_bandwidth as u8, self.modulation_index as u8, self.modulation_shaping as u8])
+    }
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum FlrcBitrateBandwidth {
+    BR1M3_BW1M2 = 0x45,
+    BR1M0_BW1M2 = 0x69,
+    BR0M65_BW0M6 = 0x86,
+    BR0M52_BW0M6 = 0xAA,
+    BR0M325_BW0M3 = 0xC7,
+    BR0M26_BW0M3 = 0xEB,
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum FlrcCodingRate {
+    CR1_2 = 0x00,
+    CR3_4 = 0x02,
+    CR1_0 = 0x04,
+}
+
+pub struct SetFlrcModulationParameters{
+    pub bitrate_bandwidth: FlrcBitrateBandwidth,
+    pub coding_rate: FlrcCodingRate,
+    pub modulation_shaping: ModulationShaping,
+}
+
+impl SX1280Command<ModeFLRC> for SetFlrcModulationParameters {
+    const OPCODE: u8 = 0x8B;
+    type ArgumentsBufferType = [u8; 3];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([self.bitrate_bandwidth as u8, self.coding_rate as u8, self.modulation_shaping as u8])
+    }
+}
+
+pub struct SetBleModulationParameters{
+    pub bitrate_bandwidth: GfskBitrateBandwidth,
+    pub modulation_index: GfskModulationIndex,
+    pub modulation_shaping: ModulationShaping,
+}
+
+impl Default for SetBleModulationParameters {
+    fn default() -> Self {
+        // BLE 1M PHY
+        Self {
+            bitrate_bandwidth: GfskBitrateBandwidth::BR1M0_BW1M2,
+            modulation_index: GfskModulationIndex::MI0_5,
+            modulation_shaping: ModulationShaping::BT0_5,
+        }
+    }
+}
+
+impl SX1280Command<ModeBLE> for SetBleModulationParameters {
+    const OPCODE: u8 = 0x8B;
+    type ArgumentsBufferType = [u8; 3];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([self.bitrate_bandwidth as u8, self.modulation_index as u8, self.modulation_shaping as u8])
+    }
+}
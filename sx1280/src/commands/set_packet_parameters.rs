@@ -0,0 +1,328 @@
+use bitfield_struct::{bitfield, FromBits, IntoBits};
+use num_enum_derive::{FromPrimitive, IntoPrimitive, TryFromPrimitive};
+use crate::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::ble::ModeBLE;
+use crate::flrc::ModeFLRC;
+use crate::gfsk::ModeGFSK;
+use crate::lora::ModeLoRa;
+#[cfg(feature = "ranging")]
+use crate::ranging::ModeLoRaRanging;
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum LoRaIQMode {
+    Standard = 0x40,
+    Inverted = 0x00,
+}
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum LoRaCrcMode {
+    Enabled = 0x20,
+    Disabled = 0x00,
+}
+#[derive(Clone, Copy, Debug, TryFromPrimitive, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum LoRaHeaderType {
+    Implicit = 0x80,
+    Explicit = 0x00,
+}
+
+#[bitfield(u8, defmt = cfg(feature = "defmt"))]
+pub struct LoRaPreambleLength {
+    #[bits(4)] mantissa: u8,
+    #[bits(4)] exponent: u8
+}
+impl From<LoRaPreambleLength> for u32 {
+    fn from(value: LoRaPreambleLength) -> Self {
+        (1u32 << value.exponent()) * value.mantissa() as u32
+    }
+}
+impl LoRaPreambleLength {
+    /// Shortest encodable preamble of at least `symbols` symbols
+    pub fn at_least(symbols: u32) -> Result<Self, SX1280CommandError> {
+        (0..16u32)
+            .map(|exp| (exp, symbols.div_ceil(1 << exp)))
+            .find(|&(_, mantissa)| mantissa <= 15)
+            .map(|(exp, mantissa)| Self::new().with_exponent(exp as u8).with_mantissa(mantissa as u8))
+            .ok_or(SX1280CommandError::InvalidArgument)
+    }
+}
+
+impl TryFrom<u32> for LoRaPreambleLength {
+    type Error = SX1280CommandError;
+
+    fn try_from(mut value: u32) -> Result<Self, Self::Error> {
+        let mut exp = 0;
+        if value == 0 { return Ok(Self::new().with_exponent(0).with_mantissa(0)) }
+        while (value & 1) == 0 {
+            exp += 1;
+            value = value >> 1;
+        }
+        if exp > 15 || value > 15 { return Err(SX1280CommandError::InvalidArgument) }
+        Ok(Self::new().with_exponent(exp).with_mantissa(value as u8))
+    }
+}
+
+pub struct SetLoraPacketParameters{
+    pub iq_mode: LoRaIQMode,
+    pub crc_mode: LoRaCrcMode,
+    pub payload_length: u8,
+    pub header_type: LoRaHeaderType,
+    pub preamble_length: LoRaPreambleLength
+}
+
+impl SX1280Command<ModeLoRa> for SetLoraPacketParameters {
+    const OPCODE: u8 = 0x8C;
+    type ArgumentsBufferType = [u8; 7];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        if self.header_type.eq(&LoRaHeaderType::Explicit) && self.payload_length > 253 {
+            return Err(SX1280CommandError::InvalidArgument)
+        }
+        Ok([
+            self.preamble_length.into_bits(),
+            self.header_type as u8,
+            self.payload_length as u8,
+            self.crc_mode as u8,
+            self.iq_mode as u8,
+            0,
+            0
+        ])
+    }
+}
+
+#[cfg(feature = "ranging")]
+impl SX1280Command<ModeLoRaRanging> for SetLoraPacketParameters {
+    const OPCODE: u8 = 0x8C;
+    type ArgumentsBufferType = [u8; 7];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        if self.header_type.eq(&LoRaHeaderType::Explicit) && self.payload_length > 253 {
+            return Err(SX1280CommandError::InvalidArgument)
+        }
+        Ok([
+            self.preamble_length.into_bits(),
+            self.header_type as u8,
+            self.payload_length as u8,
+            self.crc_mode as u8,
+            self.iq_mode as u8,
+            0,
+            0
+        ])
+    }
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum PreambleLength {
+    Bits4 = 0x00,
+    Bits8 = 0x10,
+    Bits12 = 0x20,
+    Bits16 = 0x30,
+    Bits20 = 0x40,
+    Bits24 = 0x50,
+    Bits28 = 0x60,
+    Bits32 = 0x70,
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum GfskSyncWordLength {
+    Bytes1 = 0x00,
+    Bytes2 = 0x02,
+    Bytes3 = 0x04,
+    Bytes4 = 0x06,
+    Bytes5 = 0x08,
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum SyncWordMatch {
+    Disabled = 0x00,
+    SyncWord1 = 0x10,
+    SyncWord2 = 0x20,
+    SyncWord1Or2 = 0x30,
+    SyncWord3 = 0x40,
+    SyncWord1Or3 = 0x50,
+    SyncWord2Or3 = 0x60,
+    SyncWord1Or2Or3 = 0x70,
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum PacketLengthMode {
+    FixedLength = 0x00,
+    VariableLength = 0x20,
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum GfskCrcLength {
+    Disabled = 0x00,
+    Bytes1 = 0x10,
+    Bytes2 = 0x20,
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum Whitening {
+    Enabled = 0x00,
+    Disabled = 0x08,
+}
+
+pub struct SetGfskPacketParameters{
+    pub preamble_length: PreambleLength,
+    pub sync_word_length: GfskSyncWordLength,
+    pub sync_word_match: SyncWordMatch,
+    pub header_type: PacketLengthMode,
+    pub payload_length: u8,
+    pub crc_length: GfskCrcLength,
+    pub whitening: Whitening,
+}
+
+impl SX1280Command<ModeGFSK> for SetGfskPacketParameters {
+    const OPCODE: u8 = 0x8C;
+    type ArgumentsBufferType = [u8; 7];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([
+            self.preamble_length as u8,
+            self.sync_word_length as u8,
+            self.sync_word_match as u8,
+            self.header_type as u8,
+            self.payload_length,
+            self.crc_length as u8,
+            self.whitening as u8,
+        ])
+    }
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum FlrcSyncWordLength {
+    NoSync = 0x00,
+    Bytes4 = 0x04,
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum FlrcCrcLength {
+    Disabled = 0x00,
+    Bytes2 = 0x10,
+    Bytes3 = 0x20,
+    Bytes4 = 0x30,
+}
+
+pub struct SetFlrcPacketParameters{
+    pub agc_preamble_length: PreambleLength,
+    pub sync_word_length: FlrcSyncWordLength,
+    pub sync_word_match: SyncWordMatch,
+    pub header_type: PacketLengthMode,
+    pub payload_length: u8,
+    pub crc_length: FlrcCrcLength,
+    pub whitening: Whitening,
+}
+
+impl SX1280Command<ModeFLRC> for SetFlrcPacketParameters {
+    const OPCODE: u8 = 0x8C;
+    type ArgumentsBufferType = [u8; 7];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        // FLRC needs at least 8 preamble bits, handles 6 to 127 bytes payloads and does not support whitening
+        if self.agc_preamble_length == PreambleLength::Bits4 {
+            return Err(SX1280CommandError::InvalidArgument)
+        }
+        if self.payload_length < 6 || self.payload_length > 127 {
+            return Err(SX1280CommandError::InvalidArgument)
+        }
+        if self.whitening != Whitening::Disabled {
+            return Err(SX1280CommandError::InvalidArgument)
+        }
+        Ok([
+            self.agc_preamble_length as u8,
+            self.sync_word_length as u8,
+            self.sync_word_match as u8,
+            self.header_type as u8,
+            self.payload_length,
+            self.crc_length as u8,
+            self.whitening as u8,
+        ])
+    }
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum BleConnectionState {
+    MaxPayload31Bytes = 0x00,
+    MaxPayload37Bytes = 0x20,
+    TxTestMode = 0x40,
+    MaxPayload255Bytes = 0x80,
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum BleCrcField {
+    Disabled = 0x00,
+    Bytes3 = 0x10,
+}
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum BleTestPacketType {
+    Prbs9 = 0x00,
+    EyeLong1_0 = 0x04,
+    EyeShort1_0 = 0x08,
+    Prbs15 = 0x0C,
+    All1 = 0x10,
+    All0 = 0x14,
+    EyeLong0_1 = 0x18,
+    EyeShort0_1 = 0x1C,
+}
+
+pub struct SetBlePacketParameters{
+    pub connection_state: BleConnectionState,
+    pub crc_field: BleCrcField,
+    pub packet_type: BleTestPacketType,
+    pub whitening: Whitening,
+}
+
+impl SX1280Command<ModeBLE> for SetBlePacketParameters {
+    const OPCODE: u8 = 0x8C;
+    type ArgumentsBufferType = [u8; 7];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([
+            self.connection_state as u8,
+            self.crc_field as u8,
+            self.packet_type as u8,
+            self.whitening as u8,
+            0,
+            0,
+            0
+        ])
+    }
+}
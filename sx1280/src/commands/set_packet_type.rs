@@ -1,9 +1,9 @@
-use defmt::Format;
 use num_enum_derive::{IntoPrimitive, TryFromPrimitive};
-use crate::sx1280::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
-use crate::sx1280::SX1280Mode;
+use crate::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::SX1280Mode;
 
-#[derive(Clone, Copy, Format, TryFromPrimitive, IntoPrimitive)]
+#[derive(Clone, Copy, TryFromPrimitive, IntoPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PacketType {
     GFSK = 0,
@@ -0,0 +1,24 @@
+use num_enum_derive::TryFromPrimitive;
+use crate::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::ranging::ModeLoRaRanging;
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum RangingRole {
+    Slave = 0x00,
+    Master = 0x01,
+}
+
+pub struct SetRangingRoleCommand(pub RangingRole);
+
+impl SX1280Command<ModeLoRaRanging> for SetRangingRoleCommand {
+    const OPCODE: u8 = 0xA3;
+    type ArgumentsBufferType = [u8; 1];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([self.0 as u8])
+    }
+}
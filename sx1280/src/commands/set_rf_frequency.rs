@@ -1,5 +1,5 @@
-use crate::sx1280::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
-use crate::sx1280::SX1280ModeValid;
+use crate::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::SX1280ModeValid;
 
 
 pub struct SetRFFrequencyCommand<T: IntoRFFrequency>(pub T);
@@ -0,0 +1,51 @@
+use bitfield_struct::{bitfield, FromBits, IntoBits};
+use num_enum_derive::{FromPrimitive, IntoPrimitive};
+use crate::commands::{NullResponse, NullResponseBufferType, PeriodBase, SX1280Command, SX1280CommandError};
+use fugit::Duration;
+use crate::SX1280ModeValid;
+use crate::state::RadioState;
+
+#[derive(Clone, Copy, Debug, FromPrimitive, IntoPrimitive, IntoBits, FromBits)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u32)]
+pub enum RxPeriod {
+    OneShot = 0,
+    Infinite = 65535,
+    #[num_enum(catch_all)]
+    Timeout(u32),
+}
+
+pub struct SetRxModeCommand {
+    pub period: RxPeriod,
+    pub period_base: PeriodBase,
+}
+
+impl SetRxModeCommand {
+    /// RX ending with RXTXTimeout after `timeout`, counted in the finest period base able to hold it.
+    /// The largest count is reserved for `RxPeriod::Infinite`.
+    pub fn with_timeout<T, const NOM: u32, const DENOM: u32>(timeout: Duration<T, NOM, DENOM>) -> Result<Self, SX1280CommandError>
+    where
+        Duration<T, NOM, DENOM>: Into<Duration<u64, NOM, DENOM>>,
+    {
+        let (period_base, steps) = PeriodBase::for_duration(timeout, u16::MAX - 1)?;
+        Ok(Self { period: RxPeriod::Timeout(steps as u32), period_base })
+    }
+}
+
+impl<MODE: SX1280ModeValid> SX1280Command<MODE> for SetRxModeCommand {
+    const OPCODE: u8 = 0x82;
+    type ArgumentsBufferType = [u8; 3];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        if self.period.into_bits() > u16::MAX as u32 {
+            return Err(SX1280CommandError::InvalidArgument)
+        }
+        Ok([self.period_base as u8, ((self.period.into_bits()) >> 8) as u8, ((self.period.into_bits()) & 255) as u8])
+    }
+
+    fn next_state(&self) -> Option<RadioState> {
+        Some(RadioState::Rx)
+    }
+}
@@ -0,0 +1,56 @@
+use bitfield_struct::bitfield;
+use crate::commands::{NullResponse, NullResponseBufferType, PeriodBase, SX1280Command, SX1280CommandError};
+use fugit::Duration;
+use crate::SX1280ModeValid;
+use crate::state::RadioState;
+
+pub struct SetRxDCModeCommand {
+    pub sleep_period: u16,
+    pub rx_period: u16,
+    pub period_base: PeriodBase,
+}
+
+impl SetRxDCModeCommand {
+    /// Picks the finest period base in which both durations fit the 16 bit counters.
+    /// Durations are rounded up to a whole number of steps.
+    pub fn from_durations<T, const NOM: u32, const DENOM: u32>(sleep: Duration<T, NOM, DENOM>, rx_window: Duration<T, NOM, DENOM>) -> Result<Self, SX1280CommandError>
+    where
+        Duration<T, NOM, DENOM>: Into<Duration<u64, NOM, DENOM>>,
+    {
+        let sleep: Duration<u64, NOM, DENOM> = sleep.into();
+        let rx_window: Duration<u64, NOM, DENOM> = rx_window.into();
+        let (sleep, rx_window) = (sleep.to_nanos(), rx_window.to_nanos());
+        if rx_window == 0 {
+            return Err(SX1280CommandError::InvalidArgument)
+        }
+        PeriodBase::fitting(sleep.max(rx_window), u16::MAX)
+            .map(|period_base| Self {
+                sleep_period: period_base.steps(sleep) as u16,
+                rx_period: period_base.steps(rx_window) as u16,
+                period_base,
+            })
+            .ok_or(SX1280CommandError::InvalidArgument)
+    }
+}
+
+impl<MODE: SX1280ModeValid> SX1280Command<MODE> for SetRxDCModeCommand {
+    const OPCODE: u8 = 0x94;
+    type ArgumentsBufferType = [u8; 5];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([
+            self.period_base as u8,
+            ((self.rx_period) >> 8) as u8,
+            ((self.rx_period) & 255) as u8,
+            ((self.sleep_period) >> 8) as u8,
+            ((self.sleep_period) & 255) as u8
+        ])
+
+    }
+
+    fn next_state(&self) -> Option<RadioState> {
+        Some(RadioState::Rx)
+    }
+}
@@ -1,8 +1,9 @@
 use bitfield_struct::{bitfield};
-use crate::sx1280::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
-use crate::sx1280::SX1280ModeValid;
+use crate::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::SX1280ModeValid;
+use crate::state::RadioState;
 
-#[bitfield(u8, defmt=true)]
+#[bitfield(u8, defmt = cfg(feature = "defmt"))]
 pub struct SetSleepModeCommand {
     pub retain_ram: bool,
     pub retain_data_buffer: bool,
@@ -18,4 +19,8 @@ impl<MODE: SX1280ModeValid> SX1280Command<MODE> for SetSleepModeCommand {
     fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
         Ok([self.into_bits()])
     }
+
+    fn next_state(&self) -> Option<RadioState> {
+        Some(RadioState::Sleep)
+    }
 }
@@ -1,10 +1,11 @@
 use bitfield_struct::{bitfield, FromBits, IntoBits};
-use defmt::Format;
 use num_enum_derive::{FromPrimitive, IntoPrimitive, TryFromPrimitive};
-use crate::sx1280::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
-use crate::sx1280::{SX1280Mode, SX1280ModeValid};
+use crate::commands::{NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::{SX1280Mode, SX1280ModeValid};
+use crate::state::RadioState;
 
-#[derive(Clone, Copy, Debug, Format, TryFromPrimitive)]
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StandbyMode {
     StandbyRC = 0,
@@ -24,4 +25,11 @@ impl<MODE: SX1280Mode> SX1280Command<MODE> for SetStandbyModeCommand {
     fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
         Ok([self.mode as u8])
     }
+
+    fn next_state(&self) -> Option<RadioState> {
+        Some(match self.mode {
+            StandbyMode::StandbyRC => RadioState::StandbyRC,
+            StandbyMode::StandbyXOSC => RadioState::StandbyXOSC,
+        })
+    }
 }
@@ -0,0 +1,46 @@
+use bitfield_struct::{bitfield, FromBits, IntoBits};
+use num_enum_derive::{FromPrimitive, IntoPrimitive};
+use crate::commands::{NullResponse, NullResponseBufferType, PeriodBase, SX1280Command, SX1280CommandError};
+use fugit::Duration;
+use crate::SX1280ModeValid;
+use crate::state::RadioState;
+
+#[derive(Clone, Copy, Debug, FromPrimitive, IntoPrimitive, IntoBits, FromBits)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u16)]
+pub enum TxPeriod {
+    NoTimeout = 0,
+    #[num_enum(catch_all)]
+    Interval(u16),
+}
+
+pub struct SetTxModeCommand {
+    pub period: TxPeriod,
+    pub period_base: PeriodBase,
+}
+
+impl SetTxModeCommand {
+    /// TX ending with RXTXTimeout after `timeout`, counted in the finest period base able to hold it
+    pub fn with_timeout<T, const NOM: u32, const DENOM: u32>(timeout: Duration<T, NOM, DENOM>) -> Result<Self, SX1280CommandError>
+    where
+        Duration<T, NOM, DENOM>: Into<Duration<u64, NOM, DENOM>>,
+    {
+        let (period_base, steps) = PeriodBase::for_duration(timeout, u16::MAX)?;
+        Ok(Self { period: TxPeriod::Interval(steps), period_base })
+    }
+}
+
+impl<MODE: SX1280ModeValid> SX1280Command<MODE> for SetTxModeCommand {
+    const OPCODE: u8 = 0x83;
+    type ArgumentsBufferType = [u8; 3];
+    type ResponseBufferType = NullResponseBufferType;
+    type ResponseType = NullResponse;
+
+    fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
+        Ok([self.period_base as u8, ((self.period.into_bits()) >> 8) as u8, ((self.period.into_bits()) & 255) as u8])
+    }
+
+    fn next_state(&self) -> Option<RadioState> {
+        Some(RadioState::Tx)
+    }
+}
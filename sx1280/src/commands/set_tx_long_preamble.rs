@@ -1,6 +1,7 @@
-use crate::sx1280::commands::{NullArgumentsBufferType, NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
-use crate::sx1280::lora::ModeLoRa;
-use crate::sx1280::{SX1280Mode, SX1280ModeValid};
+use crate::commands::{NullArgumentsBufferType, NullResponse, NullResponseBufferType, SX1280Command, SX1280CommandError};
+use crate::lora::ModeLoRa;
+use crate::{SX1280Mode, SX1280ModeValid};
+use crate::state::RadioState;
 
 pub struct SetTXLongPreambleCommand;
 
@@ -13,4 +14,8 @@ impl<MODE: SX1280ModeValid> SX1280Command<MODE> for SetTXLongPreambleCommand {
     fn as_write_bytes(&self) -> Result<Self::ArgumentsBufferType, SX1280CommandError> {
         Ok([])
     }
+
+    fn next_state(&self) -> Option<RadioState> {
+        Some(RadioState::Tx)
+    }
 }
@@ -0,0 +1,537 @@
+use core::future::poll_fn;
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal::spi::SpiBus;
+use core::marker::PhantomData;
+use core::task::Poll;
+use embedded_hal_async::delay::DelayNs;
+use crate::{SX1280Error, SX1280Mode, SX1280ModeValid, SX1280Result, SXDevice, SX1280};
+use crate::commands::set_packet_type::SetPacketTypeCommand;
+use crate::commands::{SX1280Command, SX1280Interrupt};
+use crate::commands::clear_irq::ClearIrqCommand;
+use crate::commands::get_irq_status::GetIrqStatusCommand;
+use crate::commands::get_status::{CommandStatus, GetStatusCommand, Status};
+use crate::commands::set_irq_params::SetIRQParametersCommand;
+use crate::registers::SX1280Register;
+use crate::timer::{SX1280Timer, Timeout};
+use crate::dio::SX1280Dio;
+use crate::state::RadioState;
+use crate::uninitialized::ModeUninitialized;
+
+const STATUS_BUSY_TIMEOUT: u64 = 1000;
+
+/// Error the radio reports in `status` for the command with `opcode`, if any
+fn status_error<DEV: SXDevice>(status: Status, opcode: u8) -> Option<SX1280Error<DEV>> {
+    match status.command_status() {
+        CommandStatus::CommandTimeout => Some(SX1280Error::CommandTimeout(opcode)),
+        CommandStatus::CommandProcessError => Some(SX1280Error::CommandProcessError(opcode)),
+        CommandStatus::CommandExecutionError => Some(SX1280Error::CommandExecutionError(opcode)),
+        _ => None,
+    }
+}
+
+
+impl<'a, SPI: SpiBus<u8>, CS: OutputPin, BUSY: InputPin, RESET: OutputPin, DIO: SX1280Dio, TIMER: SX1280Timer, MODE: SX1280Mode> SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, MODE> {
+
+    pub async fn reset(mut self) -> SX1280Result<SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, ModeUninitialized>, Self> {
+        self.reset_pin.set_low()?;
+        self.timer.delay_ms(100).await;
+        self.reset_pin.set_high()?;
+        Ok(SX1280{
+            reset_pin: self.reset_pin,
+            busy_pin: self.busy_pin,
+            spi: self.spi,
+            dio: self.dio,
+            timer: self.timer,
+            state: RadioState::StandbyRC,
+            check_status: self.check_status,
+            tx_base_address: 0,
+            rx_base_address: 0,
+            implicit_header: false,
+            configured: true,
+            _phantom: PhantomData { }
+        })
+    }
+
+    pub fn ensure_not_busy(&mut self) -> SX1280Result<(), Self> {
+        if self.busy_pin.is_high()? {
+            return Err(SX1280Error::Busy)
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn __internal_wait_for_busy(&mut self) -> SX1280Result<(), Self>{
+        self.timer.delay_ms(10).await; // todo: wtf
+        while self.busy_pin.is_high()? {
+            self.timer.delay_ms(1).await; // todo: wtf
+        }
+        Ok(())
+    }
+
+    pub async fn wait_for_busy(&mut self, timeout: u64) -> SX1280Result<(), Self> {
+        // the busy line stays high while sleeping
+        if self.state == RadioState::Sleep { return Ok(()); }
+        if self.ensure_not_busy().is_ok() { return Ok(()); }
+        if timeout == 0 {return self.__internal_wait_for_busy().await}
+        let mut timer = self.timer.clone();
+        match timer.timeout_ms(timeout, self.__internal_wait_for_busy()).await{
+            Ok(r) => r,
+            Err(_) => Err(SX1280Error::Timeout)
+        }
+    }
+
+    pub async fn write_register<T: SX1280Register<MODE>>(&mut self, reg: T) -> SX1280Result<(), Self> {
+        self.write_registers(T::ADDRESS, reg.as_write_bytes().as_ref()).await
+    }
+
+    pub async fn read_register<T: SX1280Register<MODE>>(&mut self) -> SX1280Result<T, Self> {
+        let mut buffer: T::BufferType = T::BufferType::default();
+        self.read_registers(T::ADDRESS, buffer.as_mut()).await?;
+        Ok(buffer.try_into()?)
+    }
+
+    /// Reads `T`, passes it through `f` and writes the result back.
+    /// Bits `T` does not model are written back as they were read.
+    pub async fn modify_register<T: SX1280Register<MODE>, F: FnOnce(T) -> T>(&mut self, f: F) -> SX1280Result<T, Self> {
+        let reg = f(self.read_register::<T>().await?);
+        self.__internal_wait_for_busy().await?;
+        self.write_registers(T::ADDRESS, reg.as_write_bytes().as_ref()).await?;
+        Ok(reg)
+    }
+
+    /// Writes `data` to the consecutive registers starting at `address`, in a single transaction
+    pub async fn write_registers(&mut self, address: u16, data: &[u8]) -> SX1280Result<(), Self> {
+        self.__internal_prepare_state(None).await?;
+        self.ensure_not_busy()?;
+        let mut trx = self.spi.start_transaction(&mut self.timer).await?;
+        trace!("WRITE REG -> [0x18] {:?} {:?}", &address.to_be_bytes(), data);
+
+        trx.write(&[0x18u8]).map_err(|x| SX1280Error::SpiError(x))?;
+        trx.write(&address.to_be_bytes()).map_err(|x| SX1280Error::SpiError(x))?;
+        trx.write(data).map_err(|x| SX1280Error::SpiError(x))?;
+        Ok(())
+    }
+
+    /// Reads the consecutive registers starting at `address` into `data`, in a single transaction
+    pub async fn read_registers(&mut self, address: u16, data: &mut [u8]) -> SX1280Result<(), Self> {
+        self.__internal_prepare_state(None).await?;
+        self.ensure_not_busy()?;
+        let mut trx = self.spi.start_transaction(&mut self.timer).await?;
+        trace!("READ REG -> [0x19] {:?}", &address.to_be_bytes());
+
+        trx.write(&[0x19u8]).map_err(|x| SX1280Error::SpiError(x))?;
+        trx.write(&address.to_be_bytes()).map_err(|x| SX1280Error::SpiError(x))?;
+        trx.write(&[0u8]).map_err(|x| SX1280Error::SpiError(x))?;
+        trx.read(data).map_err(|x| SX1280Error::SpiError(x))?;
+        trace!("READ REG <- {:?}", data);
+        Ok(())
+    }
+
+    pub async fn write_buffer(&mut self, offset: u8, data: &[u8]) -> SX1280Result<(), Self> {
+        self.__internal_prepare_state(None).await?;
+        self.ensure_not_busy()?;
+        let mut trx = self.spi.start_transaction(&mut self.timer).await?;
+        trace!("WRITE BUF -> [0x1A, {}] {:?}", offset, data);
+
+        trx.write(&[0x1Au8, offset]).map_err(|x| SX1280Error::SpiError(x))?;
+        trx.write(data).map_err(|x| SX1280Error::SpiError(x))?;
+        Ok(())
+    }
+
+    pub async fn read_buffer(&mut self, offset: u8, data: &mut [u8]) -> SX1280Result<(), Self> {
+        self.__internal_prepare_state(None).await?;
+        self.ensure_not_busy()?;
+        let mut trx = self.spi.start_transaction(&mut self.timer).await?;
+        trace!("READ BUF -> [0x1B, {}, 0]", offset);
+
+        trx.write(&[0x1Bu8, offset, 0]).map_err(|x| SX1280Error::SpiError(x))?;
+        trx.read(data).map_err(|x| SX1280Error::SpiError(x))?;
+        trace!("READ BUF <- {:?}", data);
+
+        Ok(())
+    }
+
+    /// Process and execution errors the radio reports along the data of a Get command always fail it.
+    /// When enabled, every command not returning data is followed by a GetStatus and fails with the error
+    /// the radio reports for it, and Get commands also fail on a command timeout.
+    /// Commands entering sleep are not checked.
+    pub fn set_status_checking(&mut self, enabled: bool) {
+        self.check_status = enabled;
+    }
+
+    pub async fn command<T: SX1280Command<MODE>>(&mut self, command: T) -> SX1280Result<T::ResponseType, Self> {
+        let next = command.next_state();
+        self.__internal_prepare_state(next).await?;
+        self.ensure_not_busy()?;
+        let ret = self.__internal_command(command).await?;
+        let returns_data = !T::ResponseBufferType::default().as_ref().is_empty();
+        let is_get_status = T::OPCODE == <GetStatusCommand as SX1280Command<MODE>>::OPCODE;
+        if self.check_status && !returns_data && !is_get_status && next != Some(RadioState::Sleep) {
+            // the status of a command is only known to the next one
+            self.wait_for_busy(STATUS_BUSY_TIMEOUT).await?;
+            let status = self.__internal_command(GetStatusCommand).await?;
+            if let Some(error) = status_error(status, T::OPCODE) {
+                return Err(error)
+            }
+        }
+        if let Some(next) = next {
+            self.state = next;
+        }
+        Ok(ret)
+    }
+
+    pub(crate) async fn __internal_command<T: SX1280Command<MODE>>(&mut self, command: T) -> SX1280Result<T::ResponseType, Self> {
+        let bytes = command.as_write_bytes()?;
+        let mut ret = T::ResponseBufferType::default();
+        let mut status = [T::OPCODE];
+
+        let mut trx = self.spi.start_transaction(&mut self.timer).await?;
+        trace!("COMMAND -> {:?} {:?}", &status, &bytes.as_ref());
+        trx.transfer_in_place(&mut status).map_err(|x| SX1280Error::SpiError(x))?;
+        trx.write(bytes.as_ref()).map_err(|x| SX1280Error::SpiError(x))?;
+        let returns_data = !ret.as_ref().is_empty();
+        if returns_data {
+            // the status of the command itself comes before the data, the one clocked with the opcode is stale
+            status = [0];
+            trx.transfer_in_place(&mut status).map_err(|x| SX1280Error::SpiError(x))?;
+            trx.transfer_in_place(ret.as_mut()).map_err(|x| SX1280Error::SpiError(x))?;
+        }
+        trace!("COMMAND <- {:?} {:?}", &status, &ret.as_ref());
+
+        let status = Status::from_bits(status[0]);
+        match status_error(status, T::OPCODE) {
+            // like for every other command, timeouts are only reported with status checking
+            Some(SX1280Error::CommandTimeout(_)) if !self.check_status => {}
+            Some(error) if returns_data => return Err(error),
+            _ => {}
+        }
+        Ok((status, ret).try_into()?)
+    }
+
+    pub async fn command_and_wait<T: SX1280Command<MODE>>(&mut self, command: T, timeout: u64) -> SX1280Result<T::ResponseType, Self> {
+        let ret = self.command(command).await?;
+        self.wait_for_busy(timeout).await?;
+        Ok(ret)
+    }
+
+    pub async fn set_operating_mode<T: SX1280ModeValid>(mut self) -> SX1280Result<SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, T>, Self>{
+        let _ = self.command(SetPacketTypeCommand(T::PACKET_CONST)).await?;
+        self.__internal_wait_for_busy().await?;
+        Ok(SX1280 {
+            spi: self.spi,
+            busy_pin: self.busy_pin,
+            reset_pin: self.reset_pin,
+            dio: self.dio,
+            timer: self.timer,
+            state: self.state,
+            check_status: self.check_status,
+            tx_base_address: self.tx_base_address,
+            rx_base_address: self.rx_base_address,
+            implicit_header: self.implicit_header,
+            configured: self.configured,
+            _phantom: PhantomData::<T> { },
+        })
+    }
+}
+
+impl<'a, SPI: SpiBus<u8>, CS: OutputPin, BUSY: InputPin, RESET: OutputPin, DIO: SX1280Dio, TIMER: SX1280Timer, MODE: SX1280ModeValid> SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, MODE> {
+
+    pub async fn set_irq_parameters(&mut self, irq_mask: SX1280Interrupt, timeout: u64) -> SX1280Result<(), Self> {
+        let dio_mask = self.dio.dio_mask();
+        self.command_and_wait(SetIRQParametersCommand { irq_mask, dio_mask }, timeout).await?;
+        Ok(())
+    }
+
+    async fn __internal_wait_for_irq(&mut self, irq: SX1280Interrupt, clear: bool) -> SX1280Result<(), Self>{
+        // an interrupt left off the lines would never wake the wait, poll unless all of them are routed
+        let lines = if self.dio.routes_all(irq) { self.dio.line_irqs(irq) } else { SX1280Interrupt::empty() };
+        let mut wait_line = !lines.is_empty();
+        loop {
+            if wait_line {
+                self.dio.wait_for_line(irq).await?;
+            }
+            let irqs = self.command(GetIrqStatusCommand).await?;
+            if irqs.contains(irq) {
+                if clear {
+                    self.__internal_wait_for_busy().await?;
+                    self.command(ClearIrqCommand(irq)).await?;
+                    self.__internal_wait_for_busy().await?;
+                }
+                return Ok(())
+            }
+            // another pending interrupt holds the line high and would end every wait at once, poll until it is cleared
+            wait_line = !lines.is_empty() && !irqs.intersects(lines);
+            self.__internal_wait_for_busy().await?;
+        }
+    }
+
+    pub async fn wait_for_irq(&mut self, irq: SX1280Interrupt, clear: bool, timeout: u64) -> SX1280Result<(), Self> {
+        self.ensure_not_busy()?;
+        let mut timer = self.timer.clone();
+        match timer.timeout_ms(timeout, self.__internal_wait_for_irq(irq, clear)).await{
+            Ok(r) => r,
+            Err(_) => Err(SX1280Error::Timeout)
+        }
+    }
+
+    pub(crate) async fn __internal_wait_for_any_irq(&mut self, irq: SX1280Interrupt, clear: bool) -> SX1280Result<SX1280Interrupt, Self>{
+        // an interrupt left off the lines would never wake the wait, poll unless all of them are routed
+        let lines = if self.dio.routes_all(irq) { self.dio.line_irqs(irq) } else { SX1280Interrupt::empty() };
+        let mut wait_line = !lines.is_empty();
+        loop {
+            if wait_line {
+                self.dio.wait_for_line(irq).await?;
+            }
+            let irqs = self.command(GetIrqStatusCommand).await?;
+            if irqs.intersects(irq) {
+                if clear {
+                    self.__internal_wait_for_busy().await?;
+                    self.command(ClearIrqCommand(irq)).await?;
+                    self.__internal_wait_for_busy().await?;
+                }
+                return Ok(irqs & irq)
+            }
+            // another pending interrupt holds the line high and would end every wait at once, poll until it is cleared
+            wait_line = !lines.is_empty() && !irqs.intersects(lines);
+            self.__internal_wait_for_busy().await?;
+        }
+    }
+
+    pub async fn wait_for_any_irq(&mut self, irq: SX1280Interrupt, clear: bool, timeout: u64) -> SX1280Result<SX1280Interrupt, Self> {
+        self.ensure_not_busy()?;
+        let mut timer = self.timer.clone();
+        match timer.timeout_ms(timeout, self.__internal_wait_for_any_irq(irq, clear)).await{
+            Ok(r) => r,
+            Err(_) => Err(SX1280Error::Timeout)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{block_on, setup, setup_with_dio, SimulatedSX1280};
+    use crate::commands::get_status::CircuitMode;
+    use crate::commands::PeriodBase;
+    use crate::commands::get_instantaneous_rssi::GetInstantaneousRssiCommand;
+    use crate::commands::get_rx_buffer_status::GetRxBufferStatusCommand;
+    use crate::commands::set_tx::{SetTxModeCommand, TxPeriod};
+    use crate::commands::set_standby::{SetStandbyModeCommand, StandbyMode};
+    use crate::dio::DioPins;
+    use crate::registers::frequency_compensation_mode::FrequencyCompensationMode;
+    use crate::registers::firmware_version::FirmwareVersion;
+    use crate::registers::rx_gain::{RxGain, RxGainSensitivity};
+    use crate::registers::lora_frequency_error::LoRaFrequencyError;
+    use crate::registers::lora_sync_word::LoRaSyncWord;
+
+    fn irq_status_reads(sim: &SimulatedSX1280) -> usize {
+        sim.frames().iter().filter(|frame| frame[0] == 0x15).count()
+    }
+
+    #[test]
+    fn set_operating_mode_sends_packet_type() {
+        let (sim, _radio) = setup();
+        assert_eq!(sim.last_frame(), Some(std::vec![0x8A, 0x01]));
+        assert_eq!(sim.packet_type(), 1);
+    }
+
+    #[test]
+    fn command_returns_status() {
+        let (_, mut radio) = setup();
+        block_on(radio.command(SetStandbyModeCommand { mode: StandbyMode::StandbyXOSC })).ok().unwrap();
+        let status = block_on(radio.command(GetStatusCommand)).ok().unwrap();
+        assert!(matches!(status.circuit_mode(), CircuitMode::StandbyXOSC));
+        assert!(matches!(status.command_status(), CommandStatus::CommandOk));
+    }
+
+    #[test]
+    fn register_round_trip() {
+        let (sim, mut radio) = setup();
+        block_on(radio.write_register(FrequencyCompensationMode(0x5A))).ok().unwrap();
+        assert_eq!(sim.last_frame(), Some(std::vec![0x18, 0x09, 0x3C, 0x5A]));
+        assert_eq!(sim.register(0x93C), 0x5A);
+
+        sim.set_register(0x93C, 0xA5);
+        let reg = block_on(radio.read_register::<FrequencyCompensationMode>()).ok().unwrap();
+        assert_eq!(reg.0, 0xA5);
+    }
+
+    #[test]
+    fn buffer_round_trip() {
+        let (sim, mut radio) = setup();
+        block_on(radio.write_buffer(0xFE, &[1, 2, 3, 4])).ok().unwrap();
+        assert_eq!(sim.last_frame(), Some(std::vec![0x1A, 0xFE, 1, 2, 3, 4]));
+        assert_eq!(&sim.buffer()[0xFE..], &[1, 2]);
+        assert_eq!(&sim.buffer()[..2], &[3, 4]);
+
+        let mut data = [0u8; 4];
+        block_on(radio.read_buffer(0xFE, &mut data)).ok().unwrap();
+        assert_eq!(data, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn command_fails_while_busy() {
+        let (sim, mut radio) = setup();
+        sim.hold_busy(5_000);
+        assert!(matches!(block_on(radio.command(GetStatusCommand)), Err(SX1280Error::Busy)));
+        block_on(radio.wait_for_busy(100)).ok().unwrap();
+        assert!(block_on(radio.command(GetStatusCommand)).is_ok());
+    }
+
+    #[test]
+    fn wait_for_irq_clears_raised_interrupt() {
+        let (sim, mut radio) = setup();
+        block_on(radio.command(SetIRQParametersCommand {
+            irq_mask: SX1280Interrupt::RxDone,
+            dio_mask: [SX1280Interrupt::empty(); 3],
+        })).ok().unwrap();
+        sim.raise_irq(SX1280Interrupt::RxDone | SX1280Interrupt::TxDone);
+        assert_eq!(sim.irq_status().bits(), SX1280Interrupt::RxDone.bits());
+
+        let irq = block_on(radio.command(GetIrqStatusCommand)).ok().unwrap();
+        assert_eq!(irq.bits(), SX1280Interrupt::RxDone.bits());
+        block_on(radio.wait_for_irq(SX1280Interrupt::RxDone, true, 100)).ok().unwrap();
+        assert!(sim.irq_status().is_empty());
+    }
+
+    #[test]
+    fn wait_for_irq_times_out() {
+        let (_, mut radio) = setup();
+        block_on(radio.command(ClearIrqCommand(SX1280Interrupt::all()))).ok().unwrap();
+        let result = block_on(radio.wait_for_irq(SX1280Interrupt::TxDone, true, 100));
+        assert!(matches!(result, Err(SX1280Error::Timeout)));
+    }
+
+    #[test]
+    fn set_irq_parameters_routes_dio_lines() {
+        let (sim, mut radio) = setup_with_dio(|sim| DioPins::dio1(sim.dio_pin(1), SX1280Interrupt::TxDone | SX1280Interrupt::RxDone));
+        block_on(radio.set_irq_parameters(SX1280Interrupt::TxDone | SX1280Interrupt::RxDone, 100)).ok().unwrap();
+        assert_eq!(sim.last_frame(), Some(std::vec![0x8D, 0x00, 0x03, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00]));
+    }
+
+    #[test]
+    fn wait_for_irq_wakes_on_dio_line() {
+        let (sim, mut radio) = setup_with_dio(|sim| DioPins::dio1(sim.dio_pin(1), SX1280Interrupt::RxDone));
+        block_on(radio.set_irq_parameters(SX1280Interrupt::RxDone, 100)).ok().unwrap();
+        sim.clear_frames();
+
+        sim.raise_irq(SX1280Interrupt::RxDone);
+        block_on(radio.wait_for_irq(SX1280Interrupt::RxDone, true, 100)).ok().unwrap();
+        assert_eq!(irq_status_reads(&sim), 1);
+        assert!(sim.irq_status().is_empty());
+    }
+
+    #[test]
+    fn wait_for_irq_on_dio_line_does_not_poll() {
+        let (sim, mut radio) = setup_with_dio(|sim| DioPins::dio1(sim.dio_pin(1), SX1280Interrupt::RxDone));
+        block_on(radio.set_irq_parameters(SX1280Interrupt::RxDone, 100)).ok().unwrap();
+        sim.clear_frames();
+
+        let result = block_on(radio.wait_for_irq(SX1280Interrupt::RxDone, true, 100));
+        assert!(matches!(result, Err(SX1280Error::Timeout)));
+        assert_eq!(irq_status_reads(&sim), 0);
+    }
+
+    #[test]
+    fn wait_for_irq_polls_while_line_is_held() {
+        let (sim, mut radio) = setup_with_dio(|sim| DioPins::dio1(sim.dio_pin(1), SX1280Interrupt::TxDone | SX1280Interrupt::RxDone));
+        block_on(radio.set_irq_parameters(SX1280Interrupt::TxDone | SX1280Interrupt::RxDone, 100)).ok().unwrap();
+        sim.raise_irq(SX1280Interrupt::TxDone);
+        sim.clear_frames();
+
+        let result = block_on(radio.wait_for_irq(SX1280Interrupt::RxDone, true, 100));
+        assert!(matches!(result, Err(SX1280Error::Timeout)));
+        // one read per busy wait, not one per wake-up of the held line
+        assert!(irq_status_reads(&sim) <= 11);
+        assert_eq!(sim.irq_status().bits(), SX1280Interrupt::TxDone.bits());
+    }
+
+    #[test]
+    fn wait_for_any_irq_polls_for_unrouted_interrupts() {
+        let (sim, mut radio) = setup_with_dio(|sim| DioPins::dio1(sim.dio_pin(1), SX1280Interrupt::RxDone));
+        block_on(radio.set_irq_parameters(SX1280Interrupt::RxDone | SX1280Interrupt::RXTXTimeout, 100)).ok().unwrap();
+        sim.raise_irq(SX1280Interrupt::RXTXTimeout);
+        sim.clear_frames();
+
+        let irqs = block_on(radio.wait_for_any_irq(SX1280Interrupt::RxDone | SX1280Interrupt::RXTXTimeout, true, 100)).ok().unwrap();
+        assert_eq!(irqs.bits(), SX1280Interrupt::RXTXTimeout.bits());
+        assert!(irq_status_reads(&sim) > 0);
+    }
+
+    #[test]
+    fn register_map_encodes_multi_byte_registers() {
+        let (sim, mut radio) = setup();
+        block_on(radio.write_register(LoRaSyncWord::PRIVATE)).ok().unwrap();
+        assert_eq!(sim.last_frame(), Some(std::vec![0x18, 0x09, 0x44, 0x14, 0x24]));
+
+        sim.set_register(0x153, 0xB7);
+        sim.set_register(0x154, 0xA9);
+        assert_eq!(block_on(radio.read_register::<FirmwareVersion>()).ok().unwrap(), FirmwareVersion(0xB7A9));
+
+        sim.set_register(0x954, 0xFF);
+        sim.set_register(0x955, 0xFF);
+        sim.set_register(0x956, 0xFE);
+        assert_eq!(block_on(radio.read_register::<LoRaFrequencyError>()).ok().unwrap().0, -2);
+        sim.set_register(0x954, 0x07);
+        assert_eq!(block_on(radio.read_register::<LoRaFrequencyError>()).ok().unwrap().0, 0x7FFFE);
+    }
+
+    #[test]
+    fn burst_register_access() {
+        let (sim, mut radio) = setup();
+        block_on(radio.write_registers(0x9CE, &[1, 2, 3, 4, 5, 6])).ok().unwrap();
+        assert_eq!(sim.last_frame(), Some(std::vec![0x18, 0x09, 0xCE, 1, 2, 3, 4, 5, 6]));
+        assert_eq!(sim.register(0x9D3), 6);
+
+        let mut data = [0u8; 4];
+        block_on(radio.read_registers(0x9D0, &mut data)).ok().unwrap();
+        assert_eq!(data, [3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn modify_register_keeps_unmodelled_bits() {
+        let (sim, mut radio) = setup();
+        sim.set_register(0x891, 0x25);
+        let gain = block_on(radio.modify_register(|gain: RxGain| gain.with_sensitivity(RxGainSensitivity::HighSensitivity))).ok().unwrap();
+        assert_eq!(gain.into_bits(), 0xE5);
+        assert_eq!(sim.register(0x891), 0xE5);
+    }
+
+    #[test]
+    fn response_status_errors_are_surfaced() {
+        let (sim, mut radio) = setup();
+        sim.set_rssi_instantaneous(150);
+        assert_eq!(*block_on(radio.command(GetInstantaneousRssiCommand)).ok().unwrap(), -75.0);
+
+        sim.reject_command(0x1F, CommandStatus::CommandProcessError);
+        assert!(matches!(block_on(radio.command(GetInstantaneousRssiCommand)), Err(SX1280Error::CommandProcessError(0x1F))));
+        sim.reject_command(0x17, CommandStatus::CommandExecutionError);
+        assert!(matches!(block_on(radio.command(GetRxBufferStatusCommand)), Err(SX1280Error::CommandExecutionError(0x17))));
+        // a stale error clocked with the opcode does not fail the command
+        sim.set_command_status(CommandStatus::CommandProcessError);
+        assert!(block_on(radio.command(GetIrqStatusCommand)).is_ok());
+
+        sim.reject_command(0x15, CommandStatus::CommandTimeout);
+        assert!(block_on(radio.command(GetIrqStatusCommand)).is_ok());
+        radio.set_status_checking(true);
+        assert!(matches!(block_on(radio.command(GetIrqStatusCommand)), Err(SX1280Error::CommandTimeout(0x15))));
+    }
+
+    #[test]
+    fn status_checking_reports_refused_commands() {
+        let (sim, mut radio) = setup();
+        let tx = SetTxModeCommand { period: TxPeriod::Interval(100), period_base: PeriodBase::Base1ms };
+
+        sim.reject_command(0x83, CommandStatus::CommandProcessError);
+        block_on(radio.command(SetTxModeCommand { ..tx })).ok().unwrap();
+
+        radio.set_status_checking(true);
+        block_on(radio.command(SetStandbyModeCommand { mode: StandbyMode::StandbyRC })).ok().unwrap();
+        assert_eq!(sim.last_frame().map(|frame| frame[0]), Some(0xC0));
+        assert!(matches!(block_on(radio.command(SetTxModeCommand { ..tx })), Err(SX1280Error::CommandProcessError(0x83))));
+        assert_eq!(radio.radio_state(), RadioState::StandbyRC);
+        sim.reject_command(0x83, CommandStatus::CommandTimeout);
+        assert!(matches!(block_on(radio.command(SetTxModeCommand { ..tx })), Err(SX1280Error::CommandTimeout(0x83))));
+        sim.reject_command(0x83, CommandStatus::CommandExecutionError);
+        assert!(matches!(block_on(radio.command(SetTxModeCommand { ..tx })), Err(SX1280Error::CommandExecutionError(0x83))));
+    }
+}
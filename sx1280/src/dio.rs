@@ -0,0 +1,152 @@
+use core::convert::Infallible;
+use core::future::{pending, poll_fn, Future};
+use core::pin::pin;
+use core::task::Poll;
+use embedded_hal::digital::{Error, ErrorKind, ErrorType};
+use embedded_hal_async::digital::Wait;
+use crate::commands::SX1280Interrupt;
+
+/// DIO lines of the radio, used to wait for interrupts instead of polling the irq status
+#[allow(async_fn_in_trait)]
+pub trait SX1280Dio {
+    /// Interrupts routed to DIO1, DIO2 and DIO3, as expected by `SetIRQParametersCommand`
+    fn dio_mask(&self) -> [SX1280Interrupt; 3];
+
+    /// Whether at least one line carries any of `irq`
+    fn routes(&self, irq: SX1280Interrupt) -> bool {
+        self.dio_mask().iter().any(|mask| mask.intersects(irq))
+    }
+
+    /// Whether every interrupt of `irq` is routed to a line
+    fn routes_all(&self, irq: SX1280Interrupt) -> bool {
+        let routed = self.dio_mask().into_iter().fold(SX1280Interrupt::empty(), |routed, mask| routed | mask);
+        irq.difference(routed).is_empty()
+    }
+
+    /// Every interrupt routed to a line carrying any of `irq`
+    fn line_irqs(&self, irq: SX1280Interrupt) -> SX1280Interrupt {
+        self.dio_mask().into_iter()
+            .filter(|mask| mask.intersects(irq))
+            .fold(SX1280Interrupt::empty(), |lines, mask| lines | mask)
+    }
+
+    /// Waits until a line carrying any of `irq` is asserted.
+    /// Lines are level triggered, so an interrupt raised before the call is not missed,
+    /// but neither is any other pending interrupt sharing the line.
+    async fn wait_for_line(&mut self, irq: SX1280Interrupt) -> Result<(), ErrorKind>;
+}
+
+/// No DIO line connected, the driver polls the irq status over SPI
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoDio;
+
+impl SX1280Dio for NoDio {
+    fn dio_mask(&self) -> [SX1280Interrupt; 3] {
+        [SX1280Interrupt::empty(); 3]
+    }
+
+    async fn wait_for_line(&mut self, _irq: SX1280Interrupt) -> Result<(), ErrorKind> {
+        pending().await
+    }
+}
+
+/// Placeholder for an unconnected line in `DioPins`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoPin;
+
+impl ErrorType for NoPin {
+    type Error = Infallible;
+}
+
+impl Wait for NoPin {
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        pending().await
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        pending().await
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        pending().await
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        pending().await
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        pending().await
+    }
+}
+
+/// A connected DIO line and the interrupts routed to it
+pub struct DioPin<P: Wait> {
+    pub pin: P,
+    pub irqs: SX1280Interrupt,
+}
+
+impl<P: Wait> DioPin<P> {
+    pub fn new(pin: P, irqs: SX1280Interrupt) -> Self {
+        Self { pin, irqs }
+    }
+}
+
+pub struct DioPins<D1: Wait = NoPin, D2: Wait = NoPin, D3: Wait = NoPin> {
+    pub dio1: Option<DioPin<D1>>,
+    pub dio2: Option<DioPin<D2>>,
+    pub dio3: Option<DioPin<D3>>,
+}
+
+impl<D1: Wait> DioPins<D1, NoPin, NoPin> {
+    /// Routes `irqs` to DIO1, the usual wiring when a single line is connected
+    pub fn dio1(pin: D1, irqs: SX1280Interrupt) -> Self {
+        Self {
+            dio1: Some(DioPin::new(pin, irqs)),
+            dio2: None,
+            dio3: None,
+        }
+    }
+}
+
+impl<D1: Wait, D2: Wait, D3: Wait> SX1280Dio for DioPins<D1, D2, D3> {
+    fn dio_mask(&self) -> [SX1280Interrupt; 3] {
+        [
+            line_mask(&self.dio1),
+            line_mask(&self.dio2),
+            line_mask(&self.dio3),
+        ]
+    }
+
+    async fn wait_for_line(&mut self, irq: SX1280Interrupt) -> Result<(), ErrorKind> {
+        let mut dio1 = pin!(wait_line(&mut self.dio1, irq));
+        let mut dio2 = pin!(wait_line(&mut self.dio2, irq));
+        let mut dio3 = pin!(wait_line(&mut self.dio3, irq));
+        poll_fn(|cx| {
+            if let Poll::Ready(r) = dio1.as_mut().poll(cx) {
+                return Poll::Ready(r);
+            }
+            if let Poll::Ready(r) = dio2.as_mut().poll(cx) {
+                return Poll::Ready(r);
+            }
+            if let Poll::Ready(r) = dio3.as_mut().poll(cx) {
+                return Poll::Ready(r);
+            }
+            Poll::Pending
+        }).await
+    }
+}
+
+fn line_mask<P: Wait>(line: &Option<DioPin<P>>) -> SX1280Interrupt {
+    match line {
+        Some(line) => line.irqs,
+        None => SX1280Interrupt::empty(),
+    }
+}
+
+async fn wait_line<P: Wait>(line: &mut Option<DioPin<P>>, irq: SX1280Interrupt) -> Result<(), ErrorKind> {
+    match line {
+        Some(line) if line.irqs.intersects(irq) => line.pin.wait_for_high().await.map_err(|e| e.kind()),
+        _ => pending().await,
+    }
+}
@@ -0,0 +1,275 @@
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal::spi::SpiBus;
+use embedded_hal_async::delay::DelayNs;
+use crate::{SX1280Error, SX1280Result, SX1280};
+use crate::commands::{PeriodBase, SX1280CommandError};
+use crate::commands::get_instantaneous_rssi::GetInstantaneousRssiCommand;
+use crate::commands::set_cad_parameters::CadSymbolsNumber;
+use crate::commands::set_rx::{RxPeriod, SetRxModeCommand};
+use crate::commands::set_standby::{SetStandbyModeCommand, StandbyMode};
+use crate::dio::SX1280Dio;
+use crate::lora::{LoRaConfig, ModeLoRa};
+use crate::timer::SX1280Timer;
+
+const DUTY_CYCLE_BUSY_TIMEOUT: u64 = 1000;
+/// Interval between two RSSI samples of the RSSI listen before talk, in ms
+const RSSI_SAMPLE_INTERVAL: u32 = 1;
+
+/// Channel check done before every transmission
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ListenBeforeTalk {
+    /// The channel is busy if the instantaneous RSSI reaches `threshold` dBm within `listen_time` ms
+    Rssi { threshold: f32, listen_time: u64 },
+    /// The channel is busy if a channel activity detection finds a LoRa preamble
+    Cad(CadSymbolsNumber),
+}
+
+/// Transmission rules of a region
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RegionProfile {
+    /// Length of the sliding window in ms
+    pub window: u64,
+    /// Airtime allowed per channel within the window, in per mille of the window. 1000 disables accounting.
+    pub duty_cycle: u16,
+    pub lbt: Option<ListenBeforeTalk>,
+    /// Delay before a new attempt after the channel was found busy, in ms
+    pub backoff: u64,
+}
+
+impl RegionProfile {
+    pub const UNRESTRICTED: RegionProfile = RegionProfile {
+        window: 0,
+        duty_cycle: 1000,
+        lbt: None,
+        backoff: 0,
+    };
+
+    /// Airtime allowed per channel within the window, in µs
+    pub fn budget(&self) -> u64 {
+        self.window * self.duty_cycle as u64
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct Transmission {
+    frequency: u32,
+    /// Start of the transmission in ms
+    start: u64,
+    /// Airtime in µs
+    airtime: u64,
+}
+
+/// Airtime accounting of the last `N` transmissions, over all channels.
+/// Time is given by the caller in ms, so the limiter does not depend on a clock.
+pub struct DutyCycleLimiter<const N: usize> {
+    profile: RegionProfile,
+    transmissions: [Option<Transmission>; N],
+}
+
+impl<const N: usize> DutyCycleLimiter<N> {
+    pub fn new(profile: RegionProfile) -> Self {
+        Self {
+            profile,
+            transmissions: [None; N],
+        }
+    }
+
+    pub fn profile(&self) -> &RegionProfile {
+        &self.profile
+    }
+
+    /// Airtime used on `frequency` within the window ending at `now`, in µs
+    pub fn airtime(&self, frequency: u32, now: u64) -> u64 {
+        self.transmissions.iter()
+            .flatten()
+            .filter(|t| t.frequency == frequency && self.in_window(t, now))
+            .map(|t| t.airtime)
+            .sum()
+    }
+
+    /// Earliest time from `now` at which `airtime` µs may be sent on `frequency`,
+    /// `None` if it exceeds the budget of an empty window
+    pub fn next_slot(&self, frequency: u32, airtime: u64, now: u64) -> Option<u64> {
+        if self.unrestricted() {
+            return Some(now)
+        }
+        if airtime > self.profile.budget() {
+            return None
+        }
+        // usage only drops as transmissions leave the window, so the first instant that fits is the answer
+        self.transmissions.iter()
+            .flatten()
+            .map(|t| t.start + self.profile.window)
+            .filter(|&expiry| expiry > now)
+            .chain(core::iter::once(now))
+            .filter(|&at| self.fits(frequency, airtime, at))
+            .min()
+    }
+
+    /// Accounts for `airtime` µs sent on `frequency` from `now`.
+    /// With every slot taken by a transmission still in the window nothing is recorded,
+    /// the time at which the oldest one leaves the window is returned instead.
+    pub fn record(&mut self, frequency: u32, airtime: u64, now: u64) -> Result<(), u64> {
+        if self.unrestricted() {
+            return Ok(())
+        }
+        let Some(slot) = self.transmissions.iter().position(|t| !matches!(t, Some(t) if self.in_window(t, now))) else {
+            let oldest = self.transmissions.iter().flatten().map(|t| t.start).min().unwrap_or(now);
+            return Err(oldest + self.profile.window)
+        };
+        self.transmissions[slot] = Some(Transmission { frequency, start: now, airtime });
+        Ok(())
+    }
+
+    fn unrestricted(&self) -> bool {
+        self.profile.duty_cycle >= 1000
+    }
+
+    fn in_window(&self, transmission: &Transmission, at: u64) -> bool {
+        transmission.start + self.profile.window > at
+    }
+
+    fn fits(&self, frequency: u32, airtime: u64, at: u64) -> bool {
+        let tracked = self.transmissions.iter().flatten().filter(|t| self.in_window(t, at)).count();
+        tracked < N && self.airtime(frequency, at) + airtime <= self.profile.budget()
+    }
+}
+
+impl<'a, SPI: SpiBus<u8>, CS: OutputPin, BUSY: InputPin, RESET: OutputPin, DIO: SX1280Dio, TIMER: SX1280Timer> SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, ModeLoRa> {
+
+    /// Transmits `data` on the channel of `config` if `limiter` allows it at `now` (ms),
+    /// after listening to the channel when the region requires it.
+    /// Returns `Deferred` with the earliest time worth a new attempt otherwise.
+    /// `config` must be the one applied to the radio.
+    pub async fn transmit_limited<const N: usize>(&mut self, limiter: &mut DutyCycleLimiter<N>, config: &LoRaConfig, data: &[u8], now: u64, timeout: u64) -> SX1280Result<(), Self> {
+        self.__internal_ensure_configured()?;
+        if data.is_empty() || data.len() > u8::MAX as usize {
+            return Err(SX1280CommandError::InvalidArgument.into())
+        }
+        let airtime = config.time_on_air(data.len() as u8)?.div_ceil(1000);
+        let slot = limiter.next_slot(config.frequency, airtime, now)
+            .ok_or(SX1280CommandError::InvalidArgument)?;
+        if slot > now {
+            return Err(SX1280Error::Deferred(slot))
+        }
+
+        let profile = *limiter.profile();
+        let busy = match profile.lbt {
+            None => false,
+            Some(ListenBeforeTalk::Rssi { threshold, listen_time }) => self.__internal_rssi_above(threshold, listen_time).await?,
+            Some(ListenBeforeTalk::Cad(symbols)) => self.channel_activity_detect(symbols, timeout).await?,
+        };
+        if busy {
+            return Err(SX1280Error::Deferred(now + profile.backoff))
+        }
+
+        // counted before sending, the radio may have been on air even if the transmission fails
+        limiter.record(config.frequency, airtime, now).map_err(SX1280Error::Deferred)?;
+        self.transmit(data, timeout).await
+    }
+
+    /// Listens for `listen_time` ms and tells whether the RSSI reached `threshold` dBm meanwhile
+    async fn __internal_rssi_above(&mut self, threshold: f32, listen_time: u64) -> SX1280Result<bool, Self> {
+        self.command_and_wait(SetRxModeCommand {
+            period: RxPeriod::Infinite,
+            period_base: PeriodBase::Base1ms,
+        }, DUTY_CYCLE_BUSY_TIMEOUT).await?;
+
+        let mut above = false;
+        let mut elapsed = 0;
+        while !above {
+            self.timer.delay_ms(RSSI_SAMPLE_INTERVAL).await;
+            elapsed += RSSI_SAMPLE_INTERVAL as u64;
+            above = *self.command(GetInstantaneousRssiCommand).await? >= threshold;
+            if elapsed >= listen_time {
+                break
+            }
+        }
+
+        self.command_and_wait(SetStandbyModeCommand { mode: StandbyMode::StandbyRC }, DUTY_CYCLE_BUSY_TIMEOUT).await?;
+        Ok(above)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{block_on, setup};
+    use crate::lora::tests::lora_config;
+    use crate::state::RadioState;
+
+    #[test]
+    fn duty_cycle_limiter_tracks_channels() {
+        let profile = RegionProfile { window: 1000, duty_cycle: 10, lbt: None, backoff: 0 };
+        let mut limiter = DutyCycleLimiter::<2>::new(profile);
+        limiter.record(2_400_000_000, 6_000, 0).unwrap();
+        assert_eq!(limiter.airtime(2_400_000_000, 100), 6_000);
+        assert_eq!(limiter.next_slot(2_400_000_000, 4_000, 100), Some(100));
+        assert_eq!(limiter.next_slot(2_400_000_000, 6_000, 100), Some(1000));
+        assert_eq!(limiter.next_slot(2_450_000_000, 6_000, 100), Some(100));
+        assert_eq!(limiter.next_slot(2_400_000_000, 20_000, 100), None);
+
+        // every slot taken, the oldest has to leave the window first
+        limiter.record(2_450_000_000, 1_000, 500).unwrap();
+        assert_eq!(limiter.next_slot(2_480_000_000, 1_000, 600), Some(1000));
+        assert_eq!(limiter.airtime(2_400_000_000, 1000), 0);
+    }
+
+    #[test]
+    fn duty_cycle_limiter_keeps_airtime_when_full() {
+        let profile = RegionProfile { window: 1000, duty_cycle: 100, lbt: None, backoff: 0 };
+        let mut limiter = DutyCycleLimiter::<2>::new(profile);
+        limiter.record(2_400_000_000, 10_000, 0).unwrap();
+        limiter.record(2_400_000_000, 10_000, 100).unwrap();
+        assert_eq!(limiter.next_slot(2_400_000_000, 10_000, 200), Some(1000));
+        assert_eq!(limiter.record(2_400_000_000, 10_000, 200), Err(1000));
+        assert_eq!(limiter.airtime(2_400_000_000, 200), 20_000);
+
+        // a slot frees up once the oldest transmission leaves the window
+        limiter.record(2_400_000_000, 10_000, 1000).unwrap();
+        assert_eq!(limiter.airtime(2_400_000_000, 1000), 20_000);
+    }
+
+    #[test]
+    fn transmit_limited_defers_over_budget() {
+        let (sim, mut radio) = setup();
+        let config = lora_config();
+        let airtime = config.time_on_air(4).ok().unwrap().div_ceil(1000);
+        // a budget of exactly one packet
+        let mut limiter = DutyCycleLimiter::<4>::new(RegionProfile { window: airtime, duty_cycle: 1, lbt: None, backoff: 0 });
+
+        block_on(radio.transmit_limited(&mut limiter, &config, &[1, 2, 3, 4], 0, 100)).ok().unwrap();
+        sim.clear_frames();
+        assert!(matches!(block_on(radio.transmit_limited(&mut limiter, &config, &[1, 2, 3, 4], 1, 100)), Err(SX1280Error::Deferred(until)) if until == airtime));
+        assert!(sim.frames().is_empty());
+        block_on(radio.transmit_limited(&mut limiter, &config, &[1, 2, 3, 4], airtime, 100)).ok().unwrap();
+    }
+
+    #[test]
+    fn transmit_limited_listens_before_talking() {
+        let (sim, mut radio) = setup();
+        let profile = RegionProfile { lbt: Some(ListenBeforeTalk::Cad(CadSymbolsNumber::Cad4Symbols)), backoff: 50, ..RegionProfile::UNRESTRICTED };
+        let mut limiter = DutyCycleLimiter::<4>::new(profile);
+
+        sim.set_channel_activity(true);
+        assert!(matches!(block_on(radio.transmit_limited(&mut limiter, &lora_config(), &[1], 10, 100)), Err(SX1280Error::Deferred(60))));
+        assert!(!sim.frames().iter().any(|frame| frame[0] == 0x83));
+
+        sim.set_channel_activity(false);
+        block_on(radio.transmit_limited(&mut limiter, &lora_config(), &[1], 60, 100)).ok().unwrap();
+        assert!(sim.frames().iter().any(|frame| frame[0] == 0x83));
+
+        let profile = RegionProfile { lbt: Some(ListenBeforeTalk::Rssi { threshold: -80.0, listen_time: 5 }), ..profile };
+        let mut limiter = DutyCycleLimiter::<4>::new(profile);
+        // -60 dBm
+        sim.set_rssi_instantaneous(120);
+        assert!(matches!(block_on(radio.transmit_limited(&mut limiter, &lora_config(), &[1], 100, 100)), Err(SX1280Error::Deferred(150))));
+        assert_eq!(radio.radio_state(), RadioState::StandbyRC);
+        // -100 dBm
+        sim.set_rssi_instantaneous(200);
+        block_on(radio.transmit_limited(&mut limiter, &lora_config(), &[1], 150, 100)).ok().unwrap();
+    }
+}
@@ -1,5 +1,5 @@
-use crate::sx1280::{SX1280Mode, SX1280ModeValid};
-use crate::sx1280::commands::set_packet_type::PacketType;
+use crate::{SX1280Mode, SX1280ModeValid};
+use crate::commands::set_packet_type::PacketType;
 
 pub struct ModeFLRC;
 
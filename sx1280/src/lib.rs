@@ -0,0 +1,134 @@
+#![cfg_attr(not(any(test, feature = "mock")), no_std)]
+
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "defmt")]
+        defmt::trace!($($arg)*);
+    };
+}
+
+pub mod spi;
+pub mod registers;
+pub mod commands;
+pub mod common;
+pub mod timer;
+pub mod dio;
+pub mod state;
+pub mod uninitialized;
+pub mod lora;
+pub mod gfsk;
+pub mod flrc;
+pub mod ble;
+pub mod time_on_air;
+pub mod duty_cycle;
+#[cfg(feature = "ranging")]
+pub mod ranging;
+#[cfg(feature = "ranging")]
+pub mod ranging_filter;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+
+use core::marker::PhantomData;
+use embedded_hal::digital::{ErrorType as PinErrorType, Error as PinError, InputPin, OutputPin};
+use embedded_hal::spi::{Error as SpiErrorType, SpiBus};
+use crate::spi::{SpiDevice, SpiError};
+use crate::commands::set_packet_type::PacketType;
+use crate::commands::SX1280CommandError;
+use crate::registers::SX1280RegisterError;
+use crate::timer::SX1280Timer;
+use crate::dio::SX1280Dio;
+use crate::state::RadioState;
+
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SX1280Error<DEV: SXDevice> {
+    SpiTransactionError(SpiError<DEV::CSType>),
+    SpiError(DEV::SpiError),
+    CommandError(SX1280CommandError),
+    Busy,
+    PinError,
+    RegisterError(SX1280RegisterError),
+    Timeout,
+    InvalidTransition(RadioState, RadioState),
+    CrcError,
+    HeaderError,
+    /// Transmission refused by the duty-cycle limiter, retry at the given time in ms
+    Deferred(u64),
+    /// The radio timed out on the command with the given opcode
+    CommandTimeout(u8),
+    /// The radio could not process the command with the given opcode
+    CommandProcessError(u8),
+    /// The radio failed to execute the command with the given opcode
+    CommandExecutionError(u8),
+    /// The radio was attached and its setup is unknown to the driver until `configure` is applied
+    Unconfigured,
+    Other,
+}
+
+pub type SX1280Result<T, DEV> = Result<T, SX1280Error<DEV>>;
+
+pub trait SX1280Mode{
+}
+pub trait SX1280ModeValid : SX1280Mode {
+    const PACKET_CONST: PacketType;
+}
+
+pub trait SXDevice {
+    type CSType: PinErrorType;
+    type SpiError: SpiErrorType;
+}
+
+pub struct SX1280<'a, SPI: SpiBus<u8>, CS: OutputPin, BUSY: InputPin, RESET: OutputPin, DIO: SX1280Dio, TIMER: SX1280Timer, MODE: SX1280Mode> {
+    spi: SpiDevice<'a, SPI, CS>,
+    busy_pin: BUSY,
+    reset_pin: RESET,
+    dio: DIO,
+    timer: TIMER,
+    state: RadioState,
+    check_status: bool,
+    /// Buffer base addresses last applied with `configure`
+    tx_base_address: u8,
+    rx_base_address: u8,
+    /// Whether `configure` set up packets without header, their length is not reported by the radio
+    implicit_header: bool,
+    /// Whether the fields above match the radio, they are unknown after `attach` until `configure`
+    configured: bool,
+    _phantom: PhantomData<MODE>,
+}
+impl<'a, SPI: SpiBus<u8>, CS: OutputPin, BUSY: InputPin, RESET: OutputPin, DIO: SX1280Dio, TIMER: SX1280Timer, MODE: SX1280Mode> SXDevice for SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, MODE> {
+    type CSType = CS;
+    type SpiError = SPI::Error;
+}
+
+
+impl<DEV, ERR> From<ERR> for SX1280Error<DEV>
+where
+    ERR: PinError,
+    DEV: SXDevice,
+{
+    fn from(_: ERR) -> Self {
+        SX1280Error::PinError
+    }
+}
+
+
+impl<DEV> From<SpiError<DEV::CSType>> for SX1280Error<DEV>
+where
+    DEV: SXDevice,
+{
+    fn from(value: SpiError<DEV::CSType>) -> Self {
+        SX1280Error::SpiTransactionError(value)
+    }
+}
+
+impl<DEV: SXDevice> From<SX1280RegisterError> for SX1280Error<DEV> {
+    fn from(value: SX1280RegisterError) -> Self {
+        Self::RegisterError(value)
+    }
+}
+
+impl<DEV: SXDevice> From<SX1280CommandError> for SX1280Error<DEV> {
+    fn from(value: SX1280CommandError) -> Self {
+        Self::CommandError(value)
+    }
+}
\ No newline at end of file
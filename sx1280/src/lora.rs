@@ -0,0 +1,811 @@
+use core::future::{poll_fn, Future};
+use core::pin::pin;
+use core::task::Poll;
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal::spi::SpiBus;
+use embedded_hal_async::delay::DelayNs;
+use fugit::{Duration, MicrosDurationU64};
+use crate::{SX1280Error, SX1280Mode, SX1280ModeValid, SX1280Result, SX1280};
+use crate::commands::{PeriodBase, SX1280Command, SX1280CommandError, SX1280Interrupt};
+use crate::commands::clear_irq::ClearIrqCommand;
+use crate::commands::set_auto_fs::SetAutoFsCommand;
+use crate::commands::set_auto_tx::SetAutoTxCommand;
+use crate::commands::get_packet_status::GetPacketStatusCommand;
+use crate::commands::get_rx_buffer_status::GetRxBufferStatusCommand;
+use crate::commands::set_buffer_base_address::SetBufferBaseAddressCommand;
+use crate::commands::set_cad::SetCAD;
+use crate::commands::set_long_preamble::SetLongPreambleModeCommand;
+use crate::commands::set_cad_parameters::{CadSymbolsNumber, SetCadParametersCommand};
+use crate::commands::set_modulation_parameters::{Bandwidth, CodingRate, SetLoraModulationParameters, SpreadingFactor};
+use crate::commands::set_packet_parameters::{LoRaCrcMode, LoRaHeaderType, LoRaIQMode, LoRaPreambleLength, SetLoraPacketParameters};
+use crate::commands::set_packet_type::PacketType;
+use crate::commands::set_rf_frequency::SetRFFrequencyCommand;
+use crate::commands::set_rx::{RxPeriod, SetRxModeCommand};
+use crate::commands::set_rx_dc::SetRxDCModeCommand;
+use crate::commands::set_standby::{SetStandbyModeCommand, StandbyMode};
+use crate::commands::set_tx::{SetTxModeCommand, TxPeriod};
+use crate::commands::set_tx_parameters::{SetTxParametersCommand, TxRampTime};
+use crate::dio::SX1280Dio;
+use crate::registers::frequency_compensation_mode::FrequencyCompensationMode;
+use crate::registers::lora_payload_length::LoRaPayloadLength;
+use crate::registers::sf_additional_configuration::SFAdditionalConfiguration;
+use crate::state::RadioState;
+use crate::time_on_air::{symbol_time, time_on_air};
+use crate::timer::{SX1280Timer, Timeout, TimeoutError};
+
+const LORA_BUSY_TIMEOUT: u64 = 1000;
+/// TX base address of `receive_and_reply`, the request is received below it
+const REPLY_BASE_ADDRESS: u8 = 128;
+
+pub struct ModeLoRa;
+
+impl SX1280Mode for ModeLoRa {}
+impl SX1280ModeValid for ModeLoRa {
+    const PACKET_CONST: PacketType = PacketType::LoRa;
+}
+
+/// A received packet, its payload is the first `length` bytes of the receive buffer
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LoRaPacket {
+    pub length: usize,
+    pub rssi: f32,
+    pub snr: f32,
+}
+
+/// Complete LoRa setup, applied with `SX1280::configure`
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LoRaConfig {
+    /// RF frequency in Hz, 2.4 GHz to 2.5 GHz
+    pub frequency: u32,
+    pub tx_base_address: u8,
+    pub rx_base_address: u8,
+    pub spreading_factor: SpreadingFactor,
+    pub bandwidth: Bandwidth,
+    pub coding_rate: CodingRate,
+    /// Preamble length in symbols, must be a mantissa of 1 to 15 times a power of two
+    pub preamble_length: u32,
+    pub header_type: LoRaHeaderType,
+    pub payload_length: u8,
+    pub crc_mode: LoRaCrcMode,
+    pub iq_mode: LoRaIQMode,
+    /// TX power in dBm, -18 to 13
+    pub power: i8,
+    pub ramp: TxRampTime,
+    pub irq_mask: SX1280Interrupt,
+}
+
+impl LoRaConfig {
+    const MIN_FREQUENCY: u32 = 2_400_000_000;
+    const MAX_FREQUENCY: u32 = 2_500_000_000;
+
+    /// Checks every parameter without talking to a radio
+    pub fn validate(&self) -> Result<(), SX1280CommandError> {
+        if self.frequency < Self::MIN_FREQUENCY || self.frequency > Self::MAX_FREQUENCY {
+            return Err(SX1280CommandError::InvalidArgument)
+        }
+        <SetLoraModulationParameters as SX1280Command<ModeLoRa>>::as_write_bytes(&self.modulation_parameters())?;
+        <SetLoraPacketParameters as SX1280Command<ModeLoRa>>::as_write_bytes(&self.packet_parameters()?)?;
+        <SetTxParametersCommand as SX1280Command<ModeLoRa>>::as_write_bytes(&self.tx_parameters())?;
+        Ok(())
+    }
+
+    /// Value of the register that has to follow the spreading factor
+    pub fn sf_additional_configuration(&self) -> SFAdditionalConfiguration {
+        SFAdditionalConfiguration::for_spreading_factor(self.spreading_factor)
+    }
+
+    /// Time on air of a packet carrying `payload_length` bytes, in ns
+    pub fn time_on_air(&self, payload_length: u8) -> Result<u64, SX1280CommandError> {
+        Ok(time_on_air(
+            self.spreading_factor,
+            self.bandwidth,
+            self.coding_rate,
+            LoRaPreambleLength::try_from(self.preamble_length)?,
+            self.header_type,
+            self.crc_mode,
+            payload_length,
+        ))
+    }
+
+    pub fn rf_frequency(&self) -> SetRFFrequencyCommand<u32> {
+        SetRFFrequencyCommand(self.frequency)
+    }
+
+    pub fn buffer_base_address(&self) -> SetBufferBaseAddressCommand {
+        SetBufferBaseAddressCommand {
+            tx_base_address: self.tx_base_address,
+            rx_base_address: self.rx_base_address,
+        }
+    }
+
+    pub fn modulation_parameters(&self) -> SetLoraModulationParameters {
+        SetLoraModulationParameters {
+            spreading_factor: self.spreading_factor,
+            bandwidth: self.bandwidth,
+            coding_rate: self.coding_rate,
+        }
+    }
+
+    pub fn packet_parameters(&self) -> Result<SetLoraPacketParameters, SX1280CommandError> {
+        Ok(SetLoraPacketParameters {
+            iq_mode: self.iq_mode,
+            crc_mode: self.crc_mode,
+            payload_length: self.payload_length,
+            header_type: self.header_type,
+            preamble_length: LoRaPreambleLength::try_from(self.preamble_length)?,
+        })
+    }
+
+    pub fn tx_parameters(&self) -> SetTxParametersCommand {
+        SetTxParametersCommand {
+            power: self.power,
+            ramp: self.ramp,
+        }
+    }
+}
+
+/// Receive duty cycle of a sniffing node: the radio sleeps `sleep` then listens `rx_window`, until a preamble shows up
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SniffSchedule {
+    pub sleep: MicrosDurationU64,
+    pub rx_window: MicrosDurationU64,
+}
+
+impl SniffSchedule {
+    pub fn command(&self) -> Result<SetRxDCModeCommand, SX1280CommandError> {
+        SetRxDCModeCommand::from_durations(self.sleep, self.rx_window)
+    }
+
+    /// Preamble the transmitter must send so that a whole RX window falls inside it, whatever the phase of the receiver
+    pub fn preamble_length(&self, spreading_factor: SpreadingFactor, bandwidth: Bandwidth) -> Result<LoRaPreambleLength, SX1280CommandError> {
+        let cycle = self.sleep.to_nanos() + 2 * self.rx_window.to_nanos();
+        let symbols = cycle.div_ceil(symbol_time(spreading_factor, bandwidth));
+        LoRaPreambleLength::at_least(u32::try_from(symbols).map_err(|_| SX1280CommandError::InvalidArgument)?)
+    }
+
+    /// Fraction of the time spent listening, in per mille
+    pub fn duty_cycle(&self) -> u64 {
+        1000 * self.rx_window.ticks() / (self.sleep.ticks() + self.rx_window.ticks()).max(1)
+    }
+}
+
+impl<'a, SPI: SpiBus<u8>, CS: OutputPin, BUSY: InputPin, RESET: OutputPin, DIO: SX1280Dio, TIMER: SX1280Timer> SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, ModeLoRa> {
+
+    /// Applies `config` in the order required by the datasheet.
+    /// Nothing is sent if the configuration is invalid.
+    pub async fn configure(&mut self, config: &LoRaConfig) -> SX1280Result<(), Self> {
+        config.validate()?;
+        self.command_and_wait(config.rf_frequency(), LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(config.buffer_base_address(), LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(config.modulation_parameters(), LORA_BUSY_TIMEOUT).await?;
+        self.write_register(config.sf_additional_configuration()).await?;
+        self.wait_for_busy(LORA_BUSY_TIMEOUT).await?;
+        self.write_register(FrequencyCompensationMode(1)).await?;
+        self.wait_for_busy(LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(config.packet_parameters()?, LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(config.tx_parameters(), LORA_BUSY_TIMEOUT).await?;
+        self.set_irq_parameters(config.irq_mask, LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(ClearIrqCommand(SX1280Interrupt::all()), LORA_BUSY_TIMEOUT).await?;
+        self.tx_base_address = config.tx_base_address;
+        self.rx_base_address = config.rx_base_address;
+        self.implicit_header = config.header_type == LoRaHeaderType::Implicit;
+        self.configured = true;
+        Ok(())
+    }
+
+    /// Sends `data` from the TX base address set by `configure` and waits until it is on air.
+    /// Modulation and packet parameters must already be set, the IRQ configuration is replaced.
+    pub async fn transmit(&mut self, data: &[u8], timeout: u64) -> SX1280Result<(), Self> {
+        self.__internal_ensure_configured()?;
+        self.__internal_load_payload(data).await?;
+
+        let irq = SX1280Interrupt::TxDone | SX1280Interrupt::RXTXTimeout;
+        self.set_irq_parameters(irq, LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(ClearIrqCommand(SX1280Interrupt::all()), LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(SetTxModeCommand {
+            period: TxPeriod::NoTimeout,
+            period_base: PeriodBase::Base1ms,
+        }, LORA_BUSY_TIMEOUT).await?;
+
+        let irq = self.__internal_wait_or_abort(irq, timeout).await?;
+        if irq.contains(SX1280Interrupt::RXTXTimeout) {
+            return Err(SX1280Error::Timeout)
+        }
+        Ok(())
+    }
+
+    /// Sends `data` behind a preamble lasting `preamble`, long enough to wake up a duty-cycled receiver.
+    /// The preamble is cut short as soon as `stop` completes. A zero preamble is refused, pass the longest
+    /// one the radio can count (about 262 s) and complete `stop` to send on demand.
+    pub async fn transmit_wake_up<T, F: Future, const NOM: u32, const DENOM: u32>(&mut self, data: &[u8], preamble: Duration<T, NOM, DENOM>, stop: F, timeout: u64) -> SX1280Result<(), Self>
+    where
+        Duration<T, NOM, DENOM>: Into<Duration<u64, NOM, DENOM>>,
+    {
+        self.__internal_ensure_configured()?;
+        let (period_base, steps) = PeriodBase::for_duration(preamble, u16::MAX)?;
+
+        self.__internal_load_payload(data).await?;
+        self.command_and_wait(SetLongPreambleModeCommand(true), LORA_BUSY_TIMEOUT).await?;
+        let result = self.__internal_long_preamble(period_base, steps, stop, timeout).await;
+        self.command_and_wait(SetLongPreambleModeCommand(false), LORA_BUSY_TIMEOUT).await?;
+        result
+    }
+
+    /// Waits for a single packet and copies its payload at the start of `data`.
+    /// Modulation and packet parameters must already be set, the IRQ configuration is replaced.
+    pub async fn receive(&mut self, data: &mut [u8], timeout: u64) -> SX1280Result<LoRaPacket, Self> {
+        self.__internal_ensure_configured()?;
+        self.__internal_receive(data, SX1280Interrupt::empty(), timeout).await
+    }
+
+    /// Listens with the duty cycle of `schedule` until a packet is received.
+    /// The radio stops cycling once a preamble is detected and stays in RX for the packet.
+    /// Transmitters must use the preamble given by `SniffSchedule::preamble_length`.
+    pub async fn sniff(&mut self, schedule: &SniffSchedule, data: &mut [u8], timeout: u64) -> SX1280Result<LoRaPacket, Self> {
+        self.__internal_ensure_configured()?;
+        let command = schedule.command()?;
+        let rx_irq = SX1280Interrupt::RxDone | SX1280Interrupt::RXTXTimeout | SX1280Interrupt::HeaderError | SX1280Interrupt::CRCError;
+        self.set_irq_parameters(rx_irq | SX1280Interrupt::PreambleDetect, LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(ClearIrqCommand(SX1280Interrupt::all()), LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(command, LORA_BUSY_TIMEOUT).await?;
+
+        // the preamble and the packet behind it share the timeout
+        let mut timer = self.timer.clone();
+        let irq = timer.timeout_ms(timeout, async {
+            let irq = self.__internal_wait_for_any_irq(rx_irq | SX1280Interrupt::PreambleDetect, true).await?;
+            if irq.intersects(rx_irq) {
+                return Ok(irq)
+            }
+            // woken up by the preamble, the packet is still on its way
+            self.__internal_wait_for_any_irq(rx_irq, true).await
+        }).await;
+        let irq = self.__internal_abort_on_timeout(irq).await?;
+        self.__internal_read_packet(data, irq).await
+    }
+
+    /// Sends `request` and receives the response into `response`, both within `timeout`.
+    /// The SX1280 has no automatic switch from TX to RX, so the MCU still starts RX once TxDone is seen.
+    /// The radio waits in frequency synthesis meanwhile and the IRQs are set up for both beforehand,
+    /// leaving a single SetRx between the two: the responder has to leave that much time before replying.
+    pub async fn exchange(&mut self, request: &[u8], response: &mut [u8], timeout: u64) -> SX1280Result<LoRaPacket, Self> {
+        self.__internal_ensure_configured()?;
+        self.command_and_wait(SetAutoFsCommand(true), LORA_BUSY_TIMEOUT).await?;
+        let result = self.__internal_exchange(request, response, timeout).await;
+        self.command_and_wait(SetAutoFsCommand(false), LORA_BUSY_TIMEOUT).await?;
+        result
+    }
+
+    /// Waits for a request and lets the radio send `reply` `turnaround` µs (plus 33 µs) after it,
+    /// without going through the MCU. The reply goes out for every received packet, including
+    /// corrupted ones. Request and reply are limited to 128 bytes each, they share the data buffer:
+    /// a longer request overwrites the reply and is refused once the radio is done sending it.
+    /// With an implicit header the request is as long as the reply.
+    /// The base addresses set by `configure` are restored afterwards.
+    pub async fn receive_and_reply(&mut self, request: &mut [u8], reply: &[u8], turnaround: u16, timeout: u64) -> SX1280Result<LoRaPacket, Self> {
+        self.__internal_ensure_configured()?;
+        if reply.is_empty() || reply.len() > REPLY_BASE_ADDRESS as usize {
+            return Err(SX1280CommandError::InvalidArgument.into())
+        }
+        self.command_and_wait(SetBufferBaseAddressCommand { tx_base_address: REPLY_BASE_ADDRESS, rx_base_address: 0 }, LORA_BUSY_TIMEOUT).await?;
+        self.write_buffer(REPLY_BASE_ADDRESS, reply).await?;
+        self.wait_for_busy(LORA_BUSY_TIMEOUT).await?;
+        self.write_register(LoRaPayloadLength(reply.len() as u8)).await?;
+        self.wait_for_busy(LORA_BUSY_TIMEOUT).await?;
+
+        self.command_and_wait(SetAutoTxCommand(turnaround), LORA_BUSY_TIMEOUT).await?;
+        let length = request.len().min(REPLY_BASE_ADDRESS as usize);
+        let result = self.__internal_reply(&mut request[..length], timeout).await;
+        self.command_and_wait(SetAutoTxCommand(0), LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(SetBufferBaseAddressCommand {
+            tx_base_address: self.tx_base_address,
+            rx_base_address: self.rx_base_address,
+        }, LORA_BUSY_TIMEOUT).await?;
+        result
+    }
+
+    /// Listens for `symbols` symbols and tells whether a LoRa preamble was found.
+    /// The radio is back in standby afterwards, the IRQ configuration is replaced.
+    pub async fn channel_activity_detect(&mut self, symbols: CadSymbolsNumber, timeout: u64) -> SX1280Result<bool, Self> {
+        self.__internal_channel_activity_detect(symbols, SX1280Interrupt::empty(), timeout).await
+    }
+
+    /// Receives a packet only if a channel activity detection finds a preamble, `None` otherwise.
+    /// Meant for wake-on-radio: the transmitter preamble has to outlast the detection and the switch to RX.
+    /// The RX IRQs are set up along the detection ones, so a single SetRx follows CadDetected.
+    pub async fn cad_receive(&mut self, symbols: CadSymbolsNumber, data: &mut [u8], timeout: u64) -> SX1280Result<Option<LoRaPacket>, Self> {
+        self.__internal_ensure_configured()?;
+        let rx_irq = SX1280Interrupt::RxDone | SX1280Interrupt::RXTXTimeout | SX1280Interrupt::HeaderError | SX1280Interrupt::CRCError;
+        if !self.__internal_channel_activity_detect(symbols, rx_irq, timeout).await? {
+            return Ok(None)
+        }
+        self.command_and_wait(SetRxModeCommand {
+            period: RxPeriod::OneShot,
+            period_base: PeriodBase::Base1ms,
+        }, LORA_BUSY_TIMEOUT).await?;
+        let irq = self.__internal_wait_or_abort(rx_irq, timeout).await?;
+        self.__internal_read_packet(data, irq).await.map(Some)
+    }
+
+    /// Runs a detection with `extra_irq` enabled along the CAD ones, both are cleared before it starts
+    async fn __internal_channel_activity_detect(&mut self, symbols: CadSymbolsNumber, extra_irq: SX1280Interrupt, timeout: u64) -> SX1280Result<bool, Self> {
+        self.command_and_wait(SetCadParametersCommand { symbol_number: symbols }, LORA_BUSY_TIMEOUT).await?;
+        let irq = SX1280Interrupt::CADDone | SX1280Interrupt::CADDetect;
+        self.set_irq_parameters(irq | extra_irq, LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(ClearIrqCommand(SX1280Interrupt::all()), LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(SetCAD, LORA_BUSY_TIMEOUT).await?;
+
+        // both are raised at the end of the detection and cleared together
+        let irq = self.__internal_wait_or_abort(irq, timeout).await?;
+        // the radio is back in standby once the detection is over
+        self.state = RadioState::StandbyRC;
+        Ok(irq.contains(SX1280Interrupt::CADDetect))
+    }
+
+    async fn __internal_long_preamble<F: Future>(&mut self, period_base: PeriodBase, steps: u16, stop: F, timeout: u64) -> SX1280Result<(), Self> {
+        let irq = SX1280Interrupt::TxDone | SX1280Interrupt::RXTXTimeout;
+        self.set_irq_parameters(irq, LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(ClearIrqCommand(SX1280Interrupt::all()), LORA_BUSY_TIMEOUT).await?;
+        // in long preamble mode the TX period is the preamble duration
+        self.command_and_wait(SetTxModeCommand { period: TxPeriod::Interval(steps), period_base }, LORA_BUSY_TIMEOUT).await?;
+
+        let stopped = {
+            let mut stop = pin!(stop);
+            let mut timer = self.timer.clone();
+            let preamble_us = (period_base.step_ns() * steps as u64).div_ceil(1000);
+            let mut elapsed = pin!(timer.delay_us(preamble_us as u32));
+            poll_fn(|cx| {
+                if stop.as_mut().poll(cx).is_ready() {
+                    return Poll::Ready(true);
+                }
+                if elapsed.as_mut().poll(cx).is_ready() {
+                    return Poll::Ready(false);
+                }
+                Poll::Pending
+            }).await
+        };
+        if stopped {
+            // restarting the timer with a single step sends the payload right away
+            self.command_and_wait(SetTxModeCommand {
+                period: TxPeriod::Interval(1),
+                period_base: PeriodBase::Base15u625s,
+            }, LORA_BUSY_TIMEOUT).await?;
+        }
+
+        let irq = self.__internal_wait_or_abort(irq, timeout).await?;
+        if irq.contains(SX1280Interrupt::RXTXTimeout) {
+            return Err(SX1280Error::Timeout)
+        }
+        Ok(())
+    }
+
+    async fn __internal_exchange(&mut self, request: &[u8], response: &mut [u8], timeout: u64) -> SX1280Result<LoRaPacket, Self> {
+        self.__internal_load_payload(request).await?;
+        let tx_irq = SX1280Interrupt::TxDone | SX1280Interrupt::RXTXTimeout;
+        let rx_irq = SX1280Interrupt::RxDone | SX1280Interrupt::RXTXTimeout | SX1280Interrupt::HeaderError | SX1280Interrupt::CRCError;
+        self.set_irq_parameters(tx_irq | rx_irq, LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(ClearIrqCommand(SX1280Interrupt::all()), LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(SetTxModeCommand {
+            period: TxPeriod::NoTimeout,
+            period_base: PeriodBase::Base1ms,
+        }, LORA_BUSY_TIMEOUT).await?;
+
+        // the request and the response share the timeout
+        let mut timer = self.timer.clone();
+        let irq = timer.timeout_ms(timeout, async {
+            let irq = self.__internal_wait_for_any_irq(tx_irq, true).await?;
+            if irq.contains(SX1280Interrupt::RXTXTimeout) {
+                return Err(SX1280Error::Timeout)
+            }
+            self.state = RadioState::FrequencySynthesis;
+            self.command_and_wait(SetRxModeCommand {
+                period: RxPeriod::OneShot,
+                period_base: PeriodBase::Base1ms,
+            }, LORA_BUSY_TIMEOUT).await?;
+            self.__internal_wait_for_any_irq(rx_irq, true).await
+        }).await;
+        let irq = self.__internal_abort_on_timeout(irq).await?;
+        self.__internal_read_packet(response, irq).await
+    }
+
+    async fn __internal_reply(&mut self, request: &mut [u8], timeout: u64) -> SX1280Result<LoRaPacket, Self> {
+        let packet = self.__internal_receive(request, SX1280Interrupt::TxDone, timeout).await;
+        if matches!(packet, Err(SX1280Error::Timeout | SX1280Error::HeaderError)) {
+            // no packet ended the reception, so no reply is on its way
+            return packet
+        }
+        // corrupted and oversized requests are answered too, the radio must be done before its setup is restored
+        let irq = self.__internal_wait_or_abort(SX1280Interrupt::TxDone | SX1280Interrupt::RXTXTimeout, timeout).await?;
+        if irq.contains(SX1280Interrupt::RXTXTimeout) {
+            return Err(SX1280Error::Timeout)
+        }
+        packet
+    }
+
+    /// Writes `data` at the TX base address and sets the payload length to send
+    async fn __internal_load_payload(&mut self, data: &[u8]) -> SX1280Result<(), Self> {
+        if data.is_empty() || data.len() > u8::MAX as usize {
+            return Err(SX1280CommandError::InvalidArgument.into())
+        }
+        self.write_buffer(self.tx_base_address, data).await?;
+        self.wait_for_busy(LORA_BUSY_TIMEOUT).await?;
+        self.write_register(LoRaPayloadLength(data.len() as u8)).await?;
+        self.wait_for_busy(LORA_BUSY_TIMEOUT).await?;
+        Ok(())
+    }
+
+    /// Receives one packet in the buffer at the RX base address, `extra_irq` is enabled along the RX ones
+    async fn __internal_receive(&mut self, data: &mut [u8], extra_irq: SX1280Interrupt, timeout: u64) -> SX1280Result<LoRaPacket, Self> {
+        let irq = SX1280Interrupt::RxDone | SX1280Interrupt::RXTXTimeout | SX1280Interrupt::HeaderError | SX1280Interrupt::CRCError;
+        self.set_irq_parameters(irq | extra_irq, LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(ClearIrqCommand(SX1280Interrupt::all()), LORA_BUSY_TIMEOUT).await?;
+        self.command_and_wait(SetRxModeCommand {
+            period: RxPeriod::OneShot,
+            period_base: PeriodBase::Base1ms,
+        }, LORA_BUSY_TIMEOUT).await?;
+
+        let irq = self.__internal_wait_or_abort(irq, timeout).await?;
+        self.__internal_read_packet(data, irq).await
+    }
+
+    /// Turns the interrupts ending a reception into the received packet or the error they report
+    async fn __internal_read_packet(&mut self, data: &mut [u8], irq: SX1280Interrupt) -> SX1280Result<LoRaPacket, Self> {
+        if irq.contains(SX1280Interrupt::HeaderError) {
+            // the radio keeps listening after a corrupted header
+            self.command_and_wait(SetStandbyModeCommand { mode: StandbyMode::StandbyRC }, LORA_BUSY_TIMEOUT).await?;
+            return Err(SX1280Error::HeaderError)
+        }
+        if irq.contains(SX1280Interrupt::CRCError) {
+            return Err(SX1280Error::CrcError)
+        }
+        if irq.contains(SX1280Interrupt::RXTXTimeout) {
+            return Err(SX1280Error::Timeout)
+        }
+
+        let status = self.command_and_wait(GetRxBufferStatusCommand, LORA_BUSY_TIMEOUT).await?;
+        let length = if self.implicit_header {
+            // implicit header packets are not reported in the buffer status
+            let length = self.read_register::<LoRaPayloadLength>().await?.0;
+            self.wait_for_busy(LORA_BUSY_TIMEOUT).await?;
+            length as usize
+        } else {
+            status.rx_payload_len as usize
+        };
+        if length > data.len() {
+            return Err(SX1280CommandError::InvalidArgument.into())
+        }
+        self.read_buffer(status.rx_buffer_start_pointer, &mut data[..length]).await?;
+        self.wait_for_busy(LORA_BUSY_TIMEOUT).await?;
+
+        let packet_status = self.command_and_wait(GetPacketStatusCommand, LORA_BUSY_TIMEOUT).await?;
+        Ok(LoRaPacket {
+            length,
+            rssi: packet_status.rssi,
+            snr: packet_status.snr,
+        })
+    }
+
+    /// Refuses to rely on base addresses and header type the driver does not know, see `attach`
+    pub(crate) fn __internal_ensure_configured(&self) -> SX1280Result<(), Self> {
+        if !self.configured {
+            return Err(SX1280Error::Unconfigured)
+        }
+        Ok(())
+    }
+
+    /// Sends the radio back to standby if a wait run under `timeout_ms` gave up
+    async fn __internal_abort_on_timeout<T>(&mut self, result: Result<SX1280Result<T, Self>, TimeoutError>) -> SX1280Result<T, Self> {
+        match result {
+            Ok(r) => r,
+            Err(_) => {
+                self.command_and_wait(SetStandbyModeCommand { mode: StandbyMode::StandbyRC }, LORA_BUSY_TIMEOUT).await?;
+                Err(SX1280Error::Timeout)
+            }
+        }
+    }
+
+    /// Waits for any of `irq`, sending the radio back to standby if nothing comes
+    async fn __internal_wait_or_abort(&mut self, irq: SX1280Interrupt, timeout: u64) -> SX1280Result<SX1280Interrupt, Self> {
+        match self.wait_for_any_irq(irq, true, timeout).await {
+            Err(SX1280Error::Timeout) => {
+                self.command_and_wait(SetStandbyModeCommand { mode: StandbyMode::StandbyRC }, LORA_BUSY_TIMEOUT).await?;
+                Err(SX1280Error::Timeout)
+            }
+            r => r,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::mock::{block_on, now, setup};
+    use crate::commands::get_status::CircuitMode;
+
+    /// Valid setup the tests derive theirs from
+    pub(crate) fn lora_config() -> LoRaConfig {
+        LoRaConfig {
+            frequency: 2_495_000_000,
+            tx_base_address: 128,
+            rx_base_address: 0,
+            spreading_factor: SpreadingFactor::SF5,
+            bandwidth: Bandwidth::BW203k125Hz,
+            coding_rate: CodingRate::CR4_7,
+            preamble_length: 12,
+            header_type: LoRaHeaderType::Explicit,
+            payload_length: 64,
+            crc_mode: LoRaCrcMode::Enabled,
+            iq_mode: LoRaIQMode::Standard,
+            power: -18,
+            ramp: TxRampTime::Ramp10us,
+            irq_mask: SX1280Interrupt::TxDone | SX1280Interrupt::RXTXTimeout,
+        }
+    }
+
+    #[test]
+    fn lora_config_validates_offline() {
+        assert!(lora_config().validate().is_ok());
+        assert!(LoRaConfig { frequency: 915_000_000, ..lora_config() }.validate().is_err());
+        assert!(LoRaConfig { power: 14, ..lora_config() }.validate().is_err());
+        assert!(LoRaConfig { preamble_length: 17, ..lora_config() }.validate().is_err());
+        assert!(LoRaConfig { payload_length: 254, ..lora_config() }.validate().is_err());
+        assert!(LoRaConfig { payload_length: 254, header_type: LoRaHeaderType::Implicit, ..lora_config() }.validate().is_ok());
+    }
+
+    #[test]
+    fn sniff_schedule_picks_period_base_and_preamble() {
+        use fugit::ExtU64;
+        let schedule = SniffSchedule { sleep: 1_000_000.micros(), rx_window: 10_000.micros() };
+        let command = schedule.command().ok().unwrap();
+        assert!(matches!(command.period_base, PeriodBase::Base15u625s));
+        assert_eq!((command.sleep_period, command.rx_period), (64_000, 640));
+        assert_eq!(schedule.duty_cycle(), 9);
+        let preamble = schedule.preamble_length(SpreadingFactor::SF7, Bandwidth::BW203k125Hz).ok().unwrap();
+        assert_eq!(u32::from(preamble), 1664);
+
+        let command = SniffSchedule { sleep: 2_000_000.micros(), rx_window: 10_000.micros() }.command().ok().unwrap();
+        assert!(matches!(command.period_base, PeriodBase::Base62u5s));
+        assert_eq!((command.sleep_period, command.rx_period), (32_000, 160));
+        assert!(SniffSchedule { sleep: 300_000_000.micros(), rx_window: 10_000.micros() }.command().is_err());
+        assert!(SniffSchedule { sleep: 1_000_000.micros(), rx_window: 0.micros() }.command().is_err());
+    }
+
+    #[test]
+    fn transmit_sends_payload() {
+        let (sim, mut radio) = setup();
+        block_on(radio.transmit(&[0xCA, 0xFE], 100)).ok().unwrap();
+        assert_eq!(&sim.buffer()[..2], &[0xCA, 0xFE]);
+        assert_eq!(sim.register(0x901), 2);
+        assert!(sim.frames().iter().any(|frame| frame[0] == 0x83));
+        assert!(sim.irq_status().is_empty());
+    }
+
+    #[test]
+    fn receive_returns_packet() {
+        let (sim, mut radio) = setup();
+        sim.queue_packet(&[1, 2, 3], [0x50, 0x14, 0, 0, 0], SX1280Interrupt::empty());
+        let mut data = [0u8; 8];
+        let packet = block_on(radio.receive(&mut data, 100)).ok().unwrap();
+        assert_eq!(packet.length, 3);
+        assert_eq!(&data[..3], &[1, 2, 3]);
+        assert_eq!(packet.rssi, -40.0);
+        assert_eq!(packet.snr, 5.0);
+    }
+
+    #[test]
+    fn receive_reports_crc_error() {
+        let (sim, mut radio) = setup();
+        sim.queue_packet(&[1, 2, 3], [0; 5], SX1280Interrupt::CRCError);
+        let mut data = [0u8; 8];
+        assert!(matches!(block_on(radio.receive(&mut data, 100)), Err(SX1280Error::CrcError)));
+        assert!(sim.irq_status().is_empty());
+    }
+
+    #[test]
+    fn transmit_and_receive_keep_configured_base_addresses() {
+        let (sim, mut radio) = setup();
+        block_on(radio.configure(&LoRaConfig { rx_base_address: 64, ..lora_config() })).ok().unwrap();
+        sim.clear_frames();
+        block_on(radio.transmit(&[0xCA, 0xFE], 100)).ok().unwrap();
+        assert_eq!(&sim.buffer()[128..130], &[0xCA, 0xFE]);
+
+        sim.queue_packet(&[1, 2, 3], [0; 5], SX1280Interrupt::empty());
+        let mut data = [0u8; 8];
+        let packet = block_on(radio.receive(&mut data, 100)).ok().unwrap();
+        assert_eq!(&data[..packet.length], &[1, 2, 3]);
+        assert_eq!(&sim.buffer()[64..67], &[1, 2, 3]);
+        assert!(sim.frames().iter().all(|frame| frame[0] != 0x8F));
+    }
+
+    #[test]
+    fn receive_reads_implicit_header_length() {
+        let (sim, mut radio) = setup();
+        let config = LoRaConfig { header_type: LoRaHeaderType::Implicit, ..lora_config() };
+        block_on(radio.configure(&config)).ok().unwrap();
+        block_on(radio.write_register(LoRaPayloadLength(2))).ok().unwrap();
+        // the buffer status of an implicit header packet is not to be trusted
+        sim.queue_packet(&[7, 8, 9], [0; 5], SX1280Interrupt::empty());
+        let mut data = [0u8; 8];
+        let packet = block_on(radio.receive(&mut data, 100)).ok().unwrap();
+        assert_eq!(packet.length, 2);
+        assert_eq!(&data[..2], &[7, 8]);
+    }
+
+    #[test]
+    fn receive_times_out_to_standby() {
+        let (sim, mut radio) = setup();
+        let mut data = [0u8; 8];
+        assert!(matches!(block_on(radio.receive(&mut data, 100)), Err(SX1280Error::Timeout)));
+        assert_eq!(sim.circuit_mode(), CircuitMode::StandbyRC);
+        assert_eq!(radio.radio_state(), RadioState::StandbyRC);
+    }
+
+    #[test]
+    fn exchange_receives_response() {
+        let (sim, mut radio) = setup();
+        sim.queue_packet(&[0xAC], [0; 5], SX1280Interrupt::empty());
+        let mut response = [0u8; 4];
+        let packet = block_on(radio.exchange(&[0x01, 0x02], &mut response, 100)).ok().unwrap();
+        assert_eq!(packet.length, 1);
+        assert_eq!(response[0], 0xAC);
+
+        let frames = sim.frames();
+        let tx = frames.iter().position(|frame| frame[0] == 0x83).unwrap();
+        let rx = frames.iter().position(|frame| frame[0] == 0x82).unwrap();
+        assert!(tx < rx);
+        // only the TxDone wait between TX and RX, no state refresh nor IRQ setup
+        assert!(frames[tx + 1..rx].iter().all(|frame| matches!(frame[0], 0x15 | 0x97)));
+        assert_eq!(frames.last(), Some(&std::vec![0x9E, 0x00]));
+    }
+
+    #[test]
+    fn receive_and_reply_arms_auto_tx() {
+        let (sim, mut radio) = setup();
+        sim.queue_packet(&[0x10, 0x20], [0; 5], SX1280Interrupt::empty());
+        let mut request = [0u8; 4];
+        let packet = block_on(radio.receive_and_reply(&mut request, &[0xAA, 0xBB, 0xCC], 150, 100)).ok().unwrap();
+        assert_eq!(packet.length, 2);
+        assert_eq!(&request[..2], &[0x10, 0x20]);
+        assert_eq!(&sim.buffer()[128..131], &[0xAA, 0xBB, 0xCC]);
+        assert_eq!(sim.register(0x901), 3);
+
+        let frames = sim.frames();
+        let arm = frames.iter().position(|frame| frame[0] == 0x98).unwrap();
+        assert_eq!(frames[arm], std::vec![0x98, 0x00, 0x96]);
+        assert!(frames[arm..].iter().any(|frame| frame[0] == 0x82));
+        let end = frames.len() - 2;
+        assert_eq!(frames[end..], [std::vec![0x98, 0x00, 0x00], std::vec![0x8F, 0x00, 0x00]]);
+    }
+
+    #[test]
+    fn receive_and_reply_waits_for_reply_to_crc_error() {
+        let (sim, mut radio) = setup();
+        sim.queue_packet(&[0x10, 0x20], [0; 5], SX1280Interrupt::CRCError);
+        let mut request = [0u8; 4];
+        let result = block_on(radio.receive_and_reply(&mut request, &[0xAA], 150, 100));
+        assert!(matches!(result, Err(SX1280Error::CrcError)));
+        // TxDone was waited for and cleared before auto TX was disarmed
+        assert!(sim.irq_status().is_empty());
+        let frames = sim.frames();
+        let end = frames.len() - 2;
+        assert_eq!(frames[end - 1], std::vec![0x97, 0x40, 0x01]);
+        assert_eq!(frames[end..], [std::vec![0x98, 0x00, 0x00], std::vec![0x8F, 0x00, 0x00]]);
+    }
+
+    #[test]
+    fn receive_and_reply_refuses_request_over_reply() {
+        let (sim, mut radio) = setup();
+        sim.queue_packet(&[0x55; 129], [0; 5], SX1280Interrupt::empty());
+        let mut request = [0u8; 255];
+        let result = block_on(radio.receive_and_reply(&mut request, &[0xAA], 150, 100));
+        assert!(matches!(result, Err(SX1280Error::CommandError(_))));
+        assert!(request.iter().all(|byte| *byte == 0));
+        assert!(sim.irq_status().is_empty());
+        let frames = sim.frames();
+        let end = frames.len() - 2;
+        assert_eq!(frames[end..], [std::vec![0x98, 0x00, 0x00], std::vec![0x8F, 0x00, 0x00]]);
+    }
+
+    #[test]
+    fn receive_and_reply_rejects_large_reply() {
+        let (_, mut radio) = setup();
+        let mut request = [0u8; 4];
+        let result = block_on(radio.receive_and_reply(&mut request, &[0; 129], 150, 100));
+        assert!(matches!(result, Err(SX1280Error::CommandError(_))));
+    }
+
+    #[test]
+    fn configure_applies_whole_setup() {
+        let (sim, mut radio) = setup();
+        sim.clear_frames();
+        block_on(radio.configure(&lora_config())).ok().unwrap();
+        assert_eq!(sim.register(0x925), 0x1E);
+        assert_eq!(sim.register(0x93C), 0x01);
+        assert_eq!(sim.irq_mask(), SX1280Interrupt::TxDone | SX1280Interrupt::RXTXTimeout);
+        let opcodes: Vec<u8> = sim.frames().iter().map(|frame| frame[0]).collect();
+        assert_eq!(opcodes, std::vec![0x86, 0x8F, 0x8B, 0x18, 0x18, 0x8C, 0x8E, 0x8D, 0x97]);
+
+        sim.clear_frames();
+        let config = LoRaConfig { power: 20, ..lora_config() };
+        assert!(block_on(radio.configure(&config)).is_err());
+        assert!(sim.frames().is_empty());
+    }
+
+    #[test]
+    fn channel_activity_detect_reports_preamble() {
+        let (sim, mut radio) = setup();
+        assert!(!block_on(radio.channel_activity_detect(CadSymbolsNumber::Cad8Symbols, 100)).ok().unwrap());
+        assert!(sim.frames().contains(&std::vec![0x88, 0x60]));
+        assert_eq!(radio.radio_state(), RadioState::StandbyRC);
+
+        sim.set_channel_activity(true);
+        assert!(block_on(radio.channel_activity_detect(CadSymbolsNumber::Cad8Symbols, 100)).ok().unwrap());
+    }
+
+    #[test]
+    fn cad_receive_only_listens_on_activity() {
+        let (sim, mut radio) = setup();
+        sim.queue_packet(&[7, 8], [0x50, 0x14, 0, 0, 0], SX1280Interrupt::empty());
+        let mut data = [0u8; 8];
+        sim.clear_frames();
+        assert!(block_on(radio.cad_receive(CadSymbolsNumber::Cad4Symbols, &mut data, 100)).ok().unwrap().is_none());
+        assert!(!sim.frames().iter().any(|frame| frame[0] == 0x82));
+
+        sim.set_channel_activity(true);
+        sim.clear_frames();
+        let packet = block_on(radio.cad_receive(CadSymbolsNumber::Cad4Symbols, &mut data, 100)).ok().unwrap().unwrap();
+        assert_eq!(packet.length, 2);
+        assert_eq!(&data[..2], &[7, 8]);
+
+        // RX starts right after the detection, without setting up IRQs nor buffers again
+        let frames = sim.frames();
+        let cad = frames.iter().position(|frame| frame[0] == 0xC5).unwrap();
+        let rx = frames.iter().position(|frame| frame[0] == 0x82).unwrap();
+        assert!(frames[cad + 1..rx].iter().all(|frame| matches!(frame[0], 0x15 | 0x97)));
+    }
+
+    #[test]
+    fn sniff_receives_packet() {
+        use fugit::ExtU64;
+        let (sim, mut radio) = setup();
+        sim.queue_packet(&[4, 2], [0x50, 0x14, 0, 0, 0], SX1280Interrupt::PreambleDetect);
+        let mut data = [0u8; 8];
+        let schedule = SniffSchedule { sleep: 1_000_000.micros(), rx_window: 10_000.micros() };
+        let packet = block_on(radio.sniff(&schedule, &mut data, 100)).ok().unwrap();
+        assert_eq!(packet.length, 2);
+        assert_eq!(&data[..2], &[4, 2]);
+        assert!(sim.frames().contains(&std::vec![0x94, 0x00, 0x02, 0x80, 0xFA, 0x00]));
+    }
+
+    #[test]
+    fn transmit_wake_up_sends_long_preamble() {
+        use fugit::ExtU64;
+        let (sim, mut radio) = setup();
+        let start = now();
+        block_on(radio.transmit_wake_up(&[1, 2, 3], 50.millis(), core::future::pending::<()>(), 100)).ok().unwrap();
+        assert!(now() - start >= 50_000_000);
+        let frames = sim.frames();
+        let position = |frame: &[u8]| frames.iter().position(|f| f == frame).unwrap();
+        assert!(position(&[0x9B, 0x01]) < position(&[0x83, 0x00, 0x0C, 0x80]));
+        assert!(position(&[0x83, 0x00, 0x0C, 0x80]) < position(&[0x9B, 0x00]));
+        assert_eq!(&sim.buffer()[..3], &[1, 2, 3]);
+    }
+
+    #[test]
+    fn transmit_wake_up_stops_on_request() {
+        use fugit::ExtU64;
+        let (sim, mut radio) = setup();
+        let start = now();
+        block_on(radio.transmit_wake_up(&[1], 1.secs(), core::future::ready(()), 100)).ok().unwrap();
+        assert!(now() - start < 1_000_000);
+        assert!(sim.frames().contains(&std::vec![0x83, 0x00, 0xFA, 0x00]));
+        assert!(sim.frames().contains(&std::vec![0x83, 0x00, 0x00, 0x01]));
+
+        sim.clear_frames();
+        let result = block_on(radio.transmit_wake_up(&[1], 0.secs(), core::future::ready(()), 100));
+        assert!(matches!(result, Err(SX1280Error::CommandError(_))));
+        assert!(sim.frames().is_empty());
+    }
+}
@@ -0,0 +1,690 @@
+//! Host side simulation of the SX1280 SPI interface.
+//!
+//! `SimulatedSX1280` hands out an `SpiBus`, a chip select, a busy, a reset and DIO pins sharing the same
+//! simulated radio, so the real `SX1280` driver can be exercised on the host. The radio decodes
+//! every SPI frame when chip select is released, keeps a register space and a data buffer, answers
+//! the status byte on every transferred byte and keeps the busy pin high for a configurable time.
+//! `MockDelay` provides the clock the driver waits on: `block_on` advances it to the next pending
+//! delay whenever the driver has nothing else to do.
+
+use core::cell::{Cell, RefCell};
+use core::convert::Infallible;
+use core::future::{poll_fn, Future};
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
+use std::rc::Rc;
+use std::vec::Vec;
+use embedded_hal::digital::{ErrorType as PinErrorType, InputPin, OutputPin};
+use embedded_hal::spi::{ErrorType as SpiErrorType, SpiBus};
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+use crate::commands::get_status::{CircuitMode, CommandStatus};
+use crate::commands::{PeriodBase, SX1280Interrupt};
+use crate::timer::DelayTimeout;
+
+std::thread_local! {
+    static NOW: Cell<u64> = const { Cell::new(0) };
+    static NEXT_WAKEUP: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Simulated time in nanoseconds, one clock per test thread
+pub fn now() -> u64 {
+    NOW.with(|now| now.get())
+}
+
+fn schedule(deadline: u64) {
+    NEXT_WAKEUP.with(|next| next.set(Some(next.get().map_or(deadline, |current| current.min(deadline)))));
+}
+
+/// Delay on the simulated clock, completes once `block_on` moved the clock past its deadline
+#[derive(Clone, Copy, Default)]
+pub struct MockDelay;
+
+impl DelayNs for MockDelay {
+    async fn delay_ns(&mut self, ns: u32) {
+        let deadline = now() + ns as u64;
+        poll_fn(|_| {
+            if now() >= deadline {
+                return Poll::Ready(());
+            }
+            schedule(deadline);
+            Poll::Pending
+        }).await
+    }
+}
+
+pub type MockTimer = DelayTimeout<MockDelay>;
+
+pub fn timer() -> MockTimer {
+    DelayTimeout(MockDelay)
+}
+
+/// Runs a driver future to completion on the current thread, jumping the clock forward
+/// to the earliest pending delay every time the future stalls
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    NEXT_WAKEUP.with(|next| next.set(None));
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        let wakeup = NEXT_WAKEUP.with(|next| next.take()).expect("future stalled without a pending delay");
+        NOW.with(|now| now.set(now.get().max(wakeup)));
+    }
+}
+
+const REGISTER_SPACE: usize = 0x1000;
+/// Time the busy pin stays high while the radio wakes up, in nanoseconds
+const WAKE_UP_TIME: u64 = 1_500_000;
+
+struct SimulatedState {
+    registers: [u8; REGISTER_SPACE],
+    buffer: [u8; 256],
+    circuit_mode: CircuitMode,
+    command_status: CommandStatus,
+    /// Opcode the radio refuses, with the status it reports for it
+    rejected: Option<(u8, CommandStatus)>,
+    packet_type: u8,
+    irq_mask: u16,
+    irq_status: u16,
+    dio_mask: [u16; 3],
+    tx_base_address: u8,
+    rx_base_address: u8,
+    rx_payload_length: u8,
+    rx_start_pointer: u8,
+    packet_status: [u8; 5],
+    queued_packet: Option<(Vec<u8>, [u8; 5], u16)>,
+    auto_tx: u16,
+    auto_fs: bool,
+    rssi_instantaneous: u8,
+    channel_activity: bool,
+    long_preamble: bool,
+    /// End of the long preamble being sent, `None` while it lasts until the next SetTx
+    preamble_until: Option<u64>,
+    busy_time: u64,
+    busy_until: u64,
+    sleeping: bool,
+    selected: bool,
+    discard: bool,
+    frame: Vec<u8>,
+    frames: Vec<Vec<u8>>,
+}
+
+impl SimulatedState {
+    fn new() -> Self {
+        Self {
+            registers: [0; REGISTER_SPACE],
+            buffer: [0; 256],
+            circuit_mode: CircuitMode::StandbyRC,
+            command_status: CommandStatus::CommandOk,
+            rejected: None,
+            packet_type: 0,
+            irq_mask: 0,
+            irq_status: 0,
+            dio_mask: [0; 3],
+            tx_base_address: 0,
+            rx_base_address: 0,
+            rx_payload_length: 0,
+            rx_start_pointer: 0,
+            packet_status: [0; 5],
+            queued_packet: None,
+            auto_tx: 0,
+            auto_fs: false,
+            rssi_instantaneous: 0,
+            channel_activity: false,
+            long_preamble: false,
+            preamble_until: None,
+            busy_time: 0,
+            busy_until: 0,
+            sleeping: false,
+            selected: false,
+            discard: false,
+            frame: Vec::new(),
+            frames: Vec::new(),
+        }
+    }
+
+    fn status(&self) -> u8 {
+        self.status_with(self.command_status)
+    }
+
+    fn status_with(&self, command_status: CommandStatus) -> u8 {
+        let circuit_mode: u8 = self.circuit_mode.into();
+        let command_status: u8 = command_status.into();
+        (circuit_mode << 5) | (command_status << 2)
+    }
+
+    /// Status of a command returning data, clocked out right after its opcode
+    fn response_status(&self) -> u8 {
+        match self.rejected {
+            Some((opcode, status)) if opcode == self.frame[0] => self.status_with(status),
+            _ => self.status_with(CommandStatus::DataAvailable),
+        }
+    }
+
+    fn raise_irq(&mut self, irq: SX1280Interrupt) {
+        self.irq_status |= irq.bits() & self.irq_mask;
+    }
+
+    /// Mode the radio falls back to once TX or RX is over
+    fn fallback_mode(&self) -> CircuitMode {
+        if self.auto_fs { CircuitMode::FrequencySynthesis } else { CircuitMode::StandbyRC }
+    }
+
+    /// Byte clocked out on MISO while the `index`-th byte of the current frame is clocked in
+    fn miso(&self, index: usize) -> u8 {
+        let frame = &self.frame;
+        let data = |from: usize| index.checked_sub(from);
+        if index == 1 && matches!(frame[0], 0x15 | 0x17 | 0x1D | 0x1F | 0x03) {
+            return self.response_status();
+        }
+        match frame[0] {
+            // ReadRegister: opcode, address, NOP, data...
+            0x19 if frame.len() >= 3 => match data(4) {
+                Some(offset) => {
+                    let address = u16::from_be_bytes([frame[1], frame[2]]) as usize + offset;
+                    self.registers[address % REGISTER_SPACE]
+                }
+                None => self.status(),
+            },
+            // ReadBuffer: opcode, offset, NOP, data...
+            0x1B if frame.len() >= 2 => match data(3) {
+                Some(offset) => self.buffer[frame[1].wrapping_add(offset as u8) as usize],
+                None => self.status(),
+            },
+            // GetIrqStatus
+            0x15 => match data(2) {
+                Some(0) => (self.irq_status >> 8) as u8,
+                Some(1) => (self.irq_status & 255) as u8,
+                _ => self.status(),
+            },
+            // GetRxBufferStatus
+            0x17 => match data(2) {
+                Some(0) => self.rx_payload_length,
+                Some(1) => self.rx_start_pointer,
+                _ => self.status(),
+            },
+            // GetPacketStatus
+            0x1D => match data(2) {
+                Some(offset) if offset < 5 => self.packet_status[offset],
+                _ => self.status(),
+            },
+            // GetRssiInst
+            0x1F => match data(2) {
+                Some(0) => self.rssi_instantaneous,
+                _ => self.status(),
+            },
+            // GetPacketType
+            0x03 => match data(2) {
+                Some(0) => self.packet_type,
+                _ => self.status(),
+            },
+            _ => self.status(),
+        }
+    }
+
+    fn deliver(&mut self, payload: &[u8], packet_status: [u8; 5], irq: SX1280Interrupt) {
+        let start = self.rx_base_address;
+        for (offset, value) in payload.iter().enumerate() {
+            self.buffer[start.wrapping_add(offset as u8) as usize] = *value;
+        }
+        self.rx_start_pointer = start;
+        self.rx_payload_length = payload.len() as u8;
+        self.packet_status = packet_status;
+        self.raise_irq(SX1280Interrupt::RxDone | irq);
+    }
+
+    /// Enters RX, receiving the queued packet if any
+    fn enter_rx(&mut self) {
+        self.circuit_mode = CircuitMode::Reception;
+        if let Some((payload, packet_status, irq)) = self.queued_packet.take() {
+            self.deliver(&payload, packet_status, SX1280Interrupt::from_bits_retain(irq));
+            if self.auto_tx != 0 {
+                self.raise_irq(SX1280Interrupt::TxDone);
+            }
+            self.circuit_mode = self.fallback_mode();
+        }
+    }
+
+    fn clock(&mut self, mosi: u8) -> u8 {
+        if !self.selected {
+            return 0xFF;
+        }
+        self.frame.push(mosi);
+        self.miso(self.frame.len() - 1)
+    }
+
+    fn select(&mut self) {
+        self.selected = true;
+        self.frame.clear();
+        if self.preamble_until.is_some_and(|until| now() >= until) {
+            self.preamble_until = None;
+            self.raise_irq(SX1280Interrupt::TxDone);
+            self.circuit_mode = self.fallback_mode();
+        }
+        if self.sleeping {
+            // the falling edge wakes the radio up, the frame clocked meanwhile is ignored
+            self.sleeping = false;
+            self.discard = true;
+            self.circuit_mode = CircuitMode::StandbyRC;
+            self.busy_until = now() + WAKE_UP_TIME;
+        }
+    }
+
+    fn release(&mut self) {
+        if !self.selected {
+            return;
+        }
+        self.selected = false;
+        if core::mem::take(&mut self.discard) || self.frame.is_empty() {
+            self.frame.clear();
+            return;
+        }
+        let frame = core::mem::take(&mut self.frame);
+        self.execute(&frame);
+        self.frames.push(frame);
+        self.busy_until = now() + self.busy_time;
+    }
+
+    fn execute(&mut self, frame: &[u8]) {
+        let args = &frame[1..];
+        if let Some((_, status)) = self.rejected.filter(|(opcode, _)| *opcode == frame[0]) {
+            self.command_status = status;
+            return;
+        }
+        let mut command_status = CommandStatus::CommandOk;
+        match frame[0] {
+            0x18 if args.len() >= 2 => {
+                let address = u16::from_be_bytes([args[0], args[1]]) as usize;
+                for (offset, value) in args[2..].iter().enumerate() {
+                    self.registers[(address + offset) % REGISTER_SPACE] = *value;
+                }
+            }
+            0x1A if !args.is_empty() => {
+                for (offset, value) in args[1..].iter().enumerate() {
+                    self.buffer[args[0].wrapping_add(offset as u8) as usize] = *value;
+                }
+            }
+            0x19 | 0x1B | 0x15 | 0x17 | 0x1D | 0x1F | 0x03 => command_status = CommandStatus::DataAvailable,
+            0xC0 => return,
+            0x80 if args.len() == 1 => {
+                self.circuit_mode = if args[0] == 0 { CircuitMode::StandbyRC } else { CircuitMode::StandbyXOSC };
+            }
+            0x84 if args.len() == 1 => {
+                self.circuit_mode = CircuitMode::Unknown;
+                self.sleeping = true;
+            }
+            0xC1 => self.circuit_mode = CircuitMode::FrequencySynthesis,
+            0x83 if args.len() == 3 && self.long_preamble => {
+                // the packet follows once the period elapses, a new SetTx restarts it
+                let base = PeriodBase::try_from(args[0]).unwrap_or(PeriodBase::Base1ms);
+                let count = u16::from_be_bytes([args[1], args[2]]) as u64;
+                self.preamble_until = (count != 0).then(|| now() + count * base.step_ns());
+                self.circuit_mode = CircuitMode::Transmission;
+            }
+            0x83 if args.len() == 3 => {
+                self.raise_irq(SX1280Interrupt::TxDone);
+                self.circuit_mode = self.fallback_mode();
+                command_status = CommandStatus::TxDone;
+            }
+            0x82 if args.len() == 3 => self.enter_rx(),
+            0x94 if args.len() == 5 => self.enter_rx(),
+            0xC5 => {
+                let detect = if self.channel_activity { SX1280Interrupt::CADDetect } else { SX1280Interrupt::empty() };
+                self.raise_irq(SX1280Interrupt::CADDone | detect);
+                self.circuit_mode = CircuitMode::StandbyRC;
+            }
+            0x8A if args.len() == 1 => self.packet_type = args[0],
+            0x8F if args.len() == 2 => {
+                self.tx_base_address = args[0];
+                self.rx_base_address = args[1];
+            }
+            0x8D if args.len() == 8 => {
+                self.irq_mask = u16::from_be_bytes([args[0], args[1]]);
+                for (line, mask) in self.dio_mask.iter_mut().enumerate() {
+                    *mask = u16::from_be_bytes([args[2 + 2 * line], args[3 + 2 * line]]);
+                }
+            }
+            0x98 if args.len() == 2 => self.auto_tx = u16::from_be_bytes([args[0], args[1]]),
+            0x9E if args.len() == 1 => self.auto_fs = args[0] != 0,
+            0x9B if args.len() == 1 => self.long_preamble = args[0] != 0,
+            0x97 if args.len() == 2 => self.irq_status &= !u16::from_be_bytes([args[0], args[1]]),
+            0x86 | 0x8B | 0x8C | 0x8E | 0x88 | 0xA3 | 0xD1 | 0xD2 => {}
+            _ => command_status = CommandStatus::CommandProcessError,
+        }
+        self.command_status = command_status;
+    }
+}
+
+/// Simulated SX1280, clone the handles it gives out to wire it to the driver
+#[derive(Clone)]
+pub struct SimulatedSX1280 {
+    state: Rc<RefCell<SimulatedState>>,
+}
+
+impl Default for SimulatedSX1280 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulatedSX1280 {
+    pub fn new() -> Self {
+        Self { state: Rc::new(RefCell::new(SimulatedState::new())) }
+    }
+
+    pub fn spi(&self) -> SimulatedSpi {
+        SimulatedSpi { state: self.state.clone() }
+    }
+
+    pub fn chip_select(&self) -> SimulatedChipSelect {
+        SimulatedChipSelect { state: self.state.clone() }
+    }
+
+    pub fn busy_pin(&self) -> SimulatedBusy {
+        SimulatedBusy { state: self.state.clone() }
+    }
+
+    pub fn reset_pin(&self) -> SimulatedReset {
+        SimulatedReset { state: self.state.clone() }
+    }
+
+    /// DIO line `line` (1 to 3), high while any interrupt routed to it is pending
+    pub fn dio_pin(&self, line: u8) -> SimulatedDio {
+        assert!((1..=3).contains(&line), "the SX1280 has DIO1 to DIO3");
+        SimulatedDio { state: self.state.clone(), line: line as usize - 1 }
+    }
+
+    /// Time in microseconds the busy pin stays high after every frame
+    pub fn set_busy_time(&self, micros: u64) {
+        self.state.borrow_mut().busy_time = micros * 1000;
+    }
+
+    /// Holds the busy pin high for `micros` starting now
+    pub fn hold_busy(&self, micros: u64) {
+        self.state.borrow_mut().busy_until = now() + micros * 1000;
+    }
+
+    pub fn register(&self, address: u16) -> u8 {
+        self.state.borrow().registers[address as usize % REGISTER_SPACE]
+    }
+
+    pub fn set_register(&self, address: u16, value: u8) {
+        self.state.borrow_mut().registers[address as usize % REGISTER_SPACE] = value;
+    }
+
+    pub fn buffer(&self) -> [u8; 256] {
+        self.state.borrow().buffer
+    }
+
+    pub fn set_buffer(&self, data: &[u8]) {
+        self.state.borrow_mut().buffer[..data.len()].copy_from_slice(data);
+    }
+
+    pub fn packet_type(&self) -> u8 {
+        self.state.borrow().packet_type
+    }
+
+    pub fn circuit_mode(&self) -> CircuitMode {
+        self.state.borrow().circuit_mode
+    }
+
+    pub fn is_sleeping(&self) -> bool {
+        self.state.borrow().sleeping
+    }
+
+    /// Moves the radio to `mode`, as it does on its own when an operation completes
+    pub fn set_circuit_mode(&self, mode: CircuitMode) {
+        self.state.borrow_mut().circuit_mode = mode;
+    }
+
+    pub fn set_command_status(&self, status: CommandStatus) {
+        self.state.borrow_mut().command_status = status;
+    }
+
+    /// Makes the radio refuse every command with `opcode`, reporting `status` for it
+    pub fn reject_command(&self, opcode: u8, status: CommandStatus) {
+        self.state.borrow_mut().rejected = Some((opcode, status));
+    }
+
+    pub fn irq_mask(&self) -> SX1280Interrupt {
+        SX1280Interrupt::from_bits_retain(self.state.borrow().irq_mask)
+    }
+
+    pub fn irq_status(&self) -> SX1280Interrupt {
+        SX1280Interrupt::from_bits_retain(self.state.borrow().irq_status)
+    }
+
+    /// Raises the interrupts enabled in the IRQ mask
+    pub fn raise_irq(&self, irq: SX1280Interrupt) {
+        self.state.borrow_mut().raise_irq(irq);
+    }
+
+    pub fn set_rssi_instantaneous(&self, raw: u8) {
+        self.state.borrow_mut().rssi_instantaneous = raw;
+    }
+
+    /// Makes the next channel activity detections find a preamble
+    pub fn set_channel_activity(&self, activity: bool) {
+        self.state.borrow_mut().channel_activity = activity;
+    }
+
+    /// Simulates the reception of `payload`, `packet_status` is returned as is by GetPacketStatus
+    pub fn receive(&self, payload: &[u8], packet_status: [u8; 5]) {
+        self.state.borrow_mut().deliver(payload, packet_status, SX1280Interrupt::empty());
+    }
+
+    /// Receives `payload` as soon as the radio enters RX, raising `irq` along with RxDone
+    pub fn queue_packet(&self, payload: &[u8], packet_status: [u8; 5], irq: SX1280Interrupt) {
+        self.state.borrow_mut().queued_packet = Some((payload.to_vec(), packet_status, irq.bits()));
+    }
+
+    /// Every MOSI frame received so far, one per chip select assertion
+    pub fn frames(&self) -> Vec<Vec<u8>> {
+        self.state.borrow().frames.clone()
+    }
+
+    pub fn last_frame(&self) -> Option<Vec<u8>> {
+        self.state.borrow().frames.last().cloned()
+    }
+
+    pub fn clear_frames(&self) {
+        self.state.borrow_mut().frames.clear();
+    }
+}
+
+pub struct SimulatedSpi {
+    state: Rc<RefCell<SimulatedState>>,
+}
+
+impl SpiErrorType for SimulatedSpi {
+    type Error = Infallible;
+}
+
+impl SpiBus<u8> for SimulatedSpi {
+    fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        let mut state = self.state.borrow_mut();
+        for word in words.iter_mut() {
+            *word = state.clock(0);
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        let mut state = self.state.borrow_mut();
+        for word in words.iter() {
+            state.clock(*word);
+        }
+        Ok(())
+    }
+
+    fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        let mut state = self.state.borrow_mut();
+        for index in 0..read.len().max(write.len()) {
+            let miso = state.clock(write.get(index).copied().unwrap_or(0));
+            if let Some(word) = read.get_mut(index) {
+                *word = miso;
+            }
+        }
+        Ok(())
+    }
+
+    fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        let mut state = self.state.borrow_mut();
+        for word in words.iter_mut() {
+            *word = state.clock(*word);
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+pub struct SimulatedChipSelect {
+    state: Rc<RefCell<SimulatedState>>,
+}
+
+impl PinErrorType for SimulatedChipSelect {
+    type Error = Infallible;
+}
+
+impl OutputPin for SimulatedChipSelect {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.state.borrow_mut().select();
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.state.borrow_mut().release();
+        Ok(())
+    }
+}
+
+pub struct SimulatedBusy {
+    state: Rc<RefCell<SimulatedState>>,
+}
+
+impl PinErrorType for SimulatedBusy {
+    type Error = Infallible;
+}
+
+impl InputPin for SimulatedBusy {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        let state = self.state.borrow();
+        Ok(state.sleeping || now() < state.busy_until)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+pub struct SimulatedDio {
+    state: Rc<RefCell<SimulatedState>>,
+    line: usize,
+}
+
+impl SimulatedDio {
+    fn level(&self) -> bool {
+        let state = self.state.borrow();
+        state.irq_status & state.dio_mask[self.line] != 0
+    }
+
+    async fn wait_for_level(&self, high: bool) {
+        poll_fn(|_| if self.level() == high { Poll::Ready(()) } else { Poll::Pending }).await
+    }
+}
+
+impl PinErrorType for SimulatedDio {
+    type Error = Infallible;
+}
+
+impl InputPin for SimulatedDio {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.level())
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.level())
+    }
+}
+
+impl Wait for SimulatedDio {
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_level(true).await;
+        Ok(())
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_level(false).await;
+        Ok(())
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_level(false).await;
+        self.wait_for_level(true).await;
+        Ok(())
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_level(true).await;
+        self.wait_for_level(false).await;
+        Ok(())
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        let level = self.level();
+        self.wait_for_level(!level).await;
+        Ok(())
+    }
+}
+
+pub struct SimulatedReset {
+    state: Rc<RefCell<SimulatedState>>,
+}
+
+impl PinErrorType for SimulatedReset {
+    type Error = Infallible;
+}
+
+impl OutputPin for SimulatedReset {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        let mut state = self.state.borrow_mut();
+        let frames = core::mem::take(&mut state.frames);
+        let busy_time = state.busy_time;
+        *state = SimulatedState::new();
+        state.frames = frames;
+        state.busy_time = busy_time;
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Driver wired to a simulated radio, as the driver tests use it
+#[cfg(test)]
+pub(crate) type TestRadio<'a, DIO, MODE> = crate::SX1280<'a, SimulatedSpi, SimulatedChipSelect, SimulatedBusy, SimulatedReset, DIO, MockTimer, MODE>;
+
+/// Simulated radio with a driver attached to it, reset and in LoRa mode
+#[cfg(test)]
+pub(crate) fn setup() -> (SimulatedSX1280, TestRadio<'static, crate::dio::NoDio, crate::lora::ModeLoRa>) {
+    setup_with_dio(|_| crate::dio::NoDio)
+}
+
+/// Same as `setup`, with the DIO lines `dio` takes from the simulated radio
+#[cfg(test)]
+pub(crate) fn setup_with_dio<DIO: crate::dio::SX1280Dio>(dio: impl FnOnce(&SimulatedSX1280) -> DIO) -> (SimulatedSX1280, TestRadio<'static, DIO, crate::lora::ModeLoRa>) {
+    let sim = SimulatedSX1280::new();
+    // the driver borrows its bus for as long as it lives, leaking it lets the test own both
+    let spi = std::boxed::Box::leak(std::boxed::Box::new(sim.spi()));
+    let dev = crate::spi::SpiDevice::new(spi, sim.chip_select()).ok().unwrap();
+    let radio = crate::SX1280::new(dev, sim.busy_pin(), sim.reset_pin(), dio(&sim), timer()).ok().unwrap();
+    let radio = block_on(radio.reset()).ok().unwrap();
+    let radio = block_on(radio.set_operating_mode::<crate::lora::ModeLoRa>()).ok().unwrap();
+    (sim, radio)
+}
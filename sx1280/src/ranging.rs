@@ -0,0 +1,119 @@
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal::spi::SpiBus;
+use crate::{SX1280Error, SX1280Mode, SX1280ModeValid, SX1280Result, SX1280};
+use crate::commands::{PeriodBase, SX1280CommandError, SX1280Interrupt};
+use crate::commands::clear_irq::ClearIrqCommand;
+use crate::commands::set_modulation_parameters::{Bandwidth, SpreadingFactor};
+use crate::commands::set_packet_type::PacketType;
+use crate::commands::set_ranging_role::{RangingRole, SetRangingRoleCommand};
+use crate::commands::set_rx::{RxPeriod, SetRxModeCommand};
+use crate::commands::set_standby::{SetStandbyModeCommand, StandbyMode};
+use crate::commands::set_tx::{SetTxModeCommand, TxPeriod};
+use crate::registers::ranging::{RangingCalibration, RangingDeviceAddress, RangingIdCheck, RangingIdCheckLength, RangingRequestAddress, RangingResult, RangingResultConfig, RangingResultFreeze, RangingResultType, RangingRssi};
+use crate::ranging_filter::RangingSample;
+use crate::timer::SX1280Timer;
+use crate::dio::SX1280Dio;
+
+const RANGING_BUSY_TIMEOUT: u64 = 1000;
+
+pub struct ModeLoRaRanging;
+
+impl SX1280Mode for ModeLoRaRanging {}
+impl SX1280ModeValid for ModeLoRaRanging {
+    const PACKET_CONST: PacketType = PacketType::Ranging;
+}
+
+impl<'a, SPI: SpiBus<u8>, CS: OutputPin, BUSY: InputPin, RESET: OutputPin, DIO: SX1280Dio, TIMER: SX1280Timer> SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, ModeLoRaRanging> {
+
+    pub async fn set_ranging_calibration(&mut self, spreading_factor: SpreadingFactor, bandwidth: Bandwidth) -> SX1280Result<(), Self> {
+        let calibration = RangingCalibration::for_parameters(spreading_factor, bandwidth)
+            .ok_or(SX1280CommandError::InvalidArgument)?;
+        self.write_register(calibration).await?;
+        self.wait_for_busy(RANGING_BUSY_TIMEOUT).await
+    }
+
+    pub async fn configure_ranging_master(&mut self, request_address: u32, spreading_factor: SpreadingFactor, bandwidth: Bandwidth) -> SX1280Result<(), Self> {
+        self.write_register(RangingRequestAddress(request_address)).await?;
+        self.wait_for_busy(RANGING_BUSY_TIMEOUT).await?;
+        self.set_ranging_calibration(spreading_factor, bandwidth).await?;
+        self.command_and_wait(SetRangingRoleCommand(RangingRole::Master), RANGING_BUSY_TIMEOUT).await?;
+        self.set_irq_parameters(SX1280Interrupt::RangingMasterResultValid | SX1280Interrupt::RangingMasterTimeout, RANGING_BUSY_TIMEOUT).await?;
+        Ok(())
+    }
+
+    pub async fn configure_ranging_slave(&mut self, device_address: u32, id_check_length: RangingIdCheckLength, spreading_factor: SpreadingFactor, bandwidth: Bandwidth) -> SX1280Result<(), Self> {
+        if !id_check_length.accepts(device_address) {
+            return Err(SX1280CommandError::InvalidArgument.into())
+        }
+        self.write_register(RangingDeviceAddress(device_address)).await?;
+        self.wait_for_busy(RANGING_BUSY_TIMEOUT).await?;
+        let id_check = self.read_register::<RangingIdCheck>().await?;
+        self.write_register(id_check.with_length(id_check_length)).await?;
+        self.wait_for_busy(RANGING_BUSY_TIMEOUT).await?;
+        self.set_ranging_calibration(spreading_factor, bandwidth).await?;
+        self.command_and_wait(SetRangingRoleCommand(RangingRole::Slave), RANGING_BUSY_TIMEOUT).await?;
+        self.set_irq_parameters(SX1280Interrupt::RangingSlaveResponseDone | SX1280Interrupt::RangingSlaveRequestDiscard | SX1280Interrupt::RangingSlaveRequestValid, RANGING_BUSY_TIMEOUT).await?;
+        Ok(())
+    }
+
+    /// Sends a ranging request and waits for the slave response.
+    /// A missing response is reported as `SX1280Error::Timeout`.
+    pub async fn ranging_request(&mut self, timeout: u64) -> SX1280Result<(), Self> {
+        self.command_and_wait(ClearIrqCommand(SX1280Interrupt::all()), RANGING_BUSY_TIMEOUT).await?;
+        self.command_and_wait(SetTxModeCommand {
+            period: TxPeriod::NoTimeout,
+            period_base: PeriodBase::Base1ms,
+        }, RANGING_BUSY_TIMEOUT).await?;
+        let irq = self.wait_for_any_irq(
+            SX1280Interrupt::RangingMasterResultValid | SX1280Interrupt::RangingMasterTimeout,
+            true,
+            timeout
+        ).await?;
+        if irq.contains(SX1280Interrupt::RangingMasterResultValid) {
+            Ok(())
+        } else {
+            Err(SX1280Error::Timeout)
+        }
+    }
+
+    /// Listens for a ranging request and answers it.
+    /// Returns `false` when a request was received but discarded because of an address mismatch.
+    pub async fn ranging_respond(&mut self, timeout: u64) -> SX1280Result<bool, Self> {
+        self.command_and_wait(ClearIrqCommand(SX1280Interrupt::all()), RANGING_BUSY_TIMEOUT).await?;
+        self.command_and_wait(SetRxModeCommand {
+            period: RxPeriod::Infinite,
+            period_base: PeriodBase::Base1ms,
+        }, RANGING_BUSY_TIMEOUT).await?;
+        let irq = self.wait_for_any_irq(
+            SX1280Interrupt::RangingSlaveResponseDone | SX1280Interrupt::RangingSlaveRequestDiscard,
+            true,
+            timeout
+        ).await?;
+        Ok(irq.contains(SX1280Interrupt::RangingSlaveResponseDone))
+    }
+
+    pub async fn read_ranging_result(&mut self, result_type: RangingResultType, bandwidth: Bandwidth) -> SX1280Result<f32, Self> {
+        self.command_and_wait(SetStandbyModeCommand { mode: StandbyMode::StandbyXOSC }, RANGING_BUSY_TIMEOUT).await?;
+
+        let freeze = self.read_register::<RangingResultFreeze>().await?;
+        self.write_register(freeze.with_freeze(true)).await?;
+        self.wait_for_busy(RANGING_BUSY_TIMEOUT).await?;
+
+        let config = self.read_register::<RangingResultConfig>().await?;
+        self.write_register(config.with_result_type(result_type)).await?;
+        self.wait_for_busy(RANGING_BUSY_TIMEOUT).await?;
+
+        let result = self.read_register::<RangingResult>().await?;
+        self.command_and_wait(SetStandbyModeCommand { mode: StandbyMode::StandbyRC }, RANGING_BUSY_TIMEOUT).await?;
+        Ok(result.to_meters(result_type, bandwidth))
+    }
+
+    pub async fn read_ranging_sample(&mut self, result_type: RangingResultType, bandwidth: Bandwidth) -> SX1280Result<RangingSample, Self> {
+        let rssi = self.read_register::<RangingRssi>().await?;
+        let distance = self.read_ranging_result(result_type, bandwidth).await?;
+        Ok(RangingSample {
+            distance,
+            rssi: rssi.dbm(),
+        })
+    }
+}
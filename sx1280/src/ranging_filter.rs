@@ -0,0 +1,249 @@
+//! Hardware independent post-processing of ranging results.
+//!
+//! Single ranging exchanges are noisy and biased by the received signal strength,
+//! samples are therefore corrected using a calibration table measured for the board and
+//! modulation parameters, then combined over a sliding window using a median filter with
+//! outlier rejection.
+
+
+/// Piecewise linear RSSI to distance bias table.
+/// Points must be sorted by increasing RSSI, values outside the table are clamped to the nearest point.
+pub struct RssiBiasCorrection<'a> {
+    /// (rssi in dBm, bias in meters to be subtracted from the measured distance)
+    pub points: &'a [(f32, f32)],
+}
+
+impl<'a> RssiBiasCorrection<'a> {
+    pub const NONE: RssiBiasCorrection<'static> = RssiBiasCorrection { points: &[] };
+
+    pub fn bias(&self, rssi: f32) -> f32 {
+        let (first, last) = match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return 0.0,
+        };
+        if rssi <= first.0 { return first.1 }
+        if rssi >= last.0 { return last.1 }
+        for window in self.points.windows(2) {
+            let (low, high) = (window[0], window[1]);
+            if rssi <= high.0 {
+                if high.0 == low.0 { return high.1 }
+                return low.1 + (high.1 - low.1) * (rssi - low.0) / (high.0 - low.0);
+            }
+        }
+        last.1
+    }
+
+    pub fn correct(&self, distance: f32, rssi: f32) -> f32 {
+        distance - self.bias(rssi)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RangingSample {
+    /// Distance in meters as read from the ranging result register
+    pub distance: f32,
+    /// RSSI in dBm of the ranging exchange
+    pub rssi: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RangingEstimate {
+    /// Estimated distance in meters
+    pub distance: f32,
+    /// Median absolute deviation of the accepted samples, in meters
+    pub spread: f32,
+    /// Between 0 and 1, the fraction of a full window that survived outlier rejection
+    pub confidence: f32,
+    /// Number of samples used for the estimate
+    pub samples: usize,
+}
+
+/// Sliding window filter over the last `N` corrected ranging samples
+pub struct RangingFilter<'a, const N: usize> {
+    correction: RssiBiasCorrection<'a>,
+    /// Samples further than `outlier_threshold` median absolute deviations from the median are rejected
+    outlier_threshold: f32,
+    /// Minimum deviation in meters always accepted, avoids rejecting everything when the window is very tight
+    min_deviation: f32,
+    window: [f32; N],
+    head: usize,
+    len: usize,
+}
+
+impl<const N: usize> RangingFilter<'static, N> {
+    /// Filter leaving samples uncorrected, the bias depends on the board and on the modulation
+    /// parameters, use `with_correction` with a table measured on your own setup
+    pub fn new(outlier_threshold: f32, min_deviation: f32) -> Self {
+        Self::with_correction(RssiBiasCorrection::NONE, outlier_threshold, min_deviation)
+    }
+}
+
+impl<'a, const N: usize> RangingFilter<'a, N> {
+    pub fn with_correction(correction: RssiBiasCorrection<'a>, outlier_threshold: f32, min_deviation: f32) -> Self {
+        Self {
+            correction,
+            outlier_threshold,
+            min_deviation,
+            window: [0.0; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub fn push(&mut self, sample: RangingSample) {
+        if N == 0 { return }
+        self.window[self.head] = self.correction.correct(sample.distance, sample.rssi);
+        self.head = (self.head + 1) % N;
+        if self.len < N { self.len += 1 }
+    }
+
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn estimate(&self) -> Option<RangingEstimate> {
+        if self.len == 0 { return None }
+
+        let mut sorted = [0.0f32; N];
+        let sorted = &mut sorted[..self.len];
+        sorted.copy_from_slice(&self.window[..self.len]);
+        let median = median(sorted);
+
+        let mut deviations = [0.0f32; N];
+        let deviations = &mut deviations[..self.len];
+        for (deviation, value) in deviations.iter_mut().zip(sorted.iter()) {
+            *deviation = abs(*value - median);
+        }
+        let mad = median(deviations);
+
+        let limit = max(self.outlier_threshold * mad, self.min_deviation);
+        let mut sum = 0.0f32;
+        let mut samples = 0usize;
+        let mut accepted = [0.0f32; N];
+        for value in sorted.iter().copied() {
+            if abs(value - median) <= limit {
+                sum += value;
+                accepted[samples] = abs(value - median);
+                samples += 1;
+            }
+        }
+        if samples == 0 { return None }
+
+        Some(RangingEstimate {
+            distance: sum / samples as f32,
+            spread: median(&mut accepted[..samples]),
+            confidence: samples as f32 / N as f32,
+            samples,
+        })
+    }
+}
+
+fn median(values: &mut [f32]) -> f32 {
+    values.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+    let middle = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[middle - 1] + values[middle]) / 2.0
+    } else {
+        values[middle]
+    }
+}
+
+fn abs(value: f32) -> f32 {
+    if value < 0.0 { -value } else { value }
+}
+
+fn max(a: f32, b: f32) -> f32 {
+    if a > b { a } else { b }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLE: RssiBiasCorrection<'static> = RssiBiasCorrection { points: &[(-100.0, 4.0), (-80.0, 2.0), (-60.0, 0.0)] };
+
+    fn sample(distance: f32) -> RangingSample {
+        RangingSample { distance, rssi: -60.0 }
+    }
+
+    fn filter<const N: usize>(distances: &[f32]) -> RangingFilter<'static, N> {
+        let mut filter = RangingFilter::with_correction(RssiBiasCorrection::NONE, 3.0, 0.5);
+        for distance in distances {
+            filter.push(sample(*distance));
+        }
+        filter
+    }
+
+    #[test]
+    fn bias_interpolates_and_clamps() {
+        assert_eq!(TABLE.bias(-90.0), 3.0);
+        assert_eq!(TABLE.bias(-65.0), 0.5);
+        assert_eq!(TABLE.bias(-80.0), 2.0);
+        assert_eq!(TABLE.bias(-120.0), 4.0);
+        assert_eq!(TABLE.bias(-30.0), 0.0);
+        assert_eq!(RssiBiasCorrection::NONE.bias(-90.0), 0.0);
+        assert_eq!(TABLE.correct(10.0, -90.0), 7.0);
+    }
+
+    #[test]
+    fn samples_are_not_corrected_by_default() {
+        let mut filter = RangingFilter::<4>::new(3.0, 0.5);
+        filter.push(RangingSample { distance: 10.0, rssi: -90.0 });
+        assert_eq!(filter.estimate().unwrap().distance, 10.0);
+
+        let mut filter = RangingFilter::<4>::with_correction(TABLE, 3.0, 0.5);
+        filter.push(RangingSample { distance: 10.0, rssi: -90.0 });
+        assert_eq!(filter.estimate().unwrap().distance, 7.0);
+    }
+
+    #[test]
+    fn median_of_odd_and_even_windows() {
+        assert_eq!(median(&mut [5.0, 1.0, 3.0]), 3.0);
+        assert_eq!(median(&mut [4.0, 1.0, 3.0, 2.0]), 2.5);
+    }
+
+    #[test]
+    fn outliers_are_rejected() {
+        // median 10.1, deviations 0.1 so anything beyond 0.5 m is dropped
+        let estimate = filter::<5>(&[10.0, 10.2, 10.1, 10.1, 25.0]).estimate().unwrap();
+        assert_eq!(estimate.samples, 4);
+        assert!((estimate.distance - 10.1).abs() < 1e-4);
+        assert_eq!(estimate.confidence, 0.8);
+
+        // a wide spread raises the limit over min_deviation, so nothing is dropped
+        let estimate = filter::<4>(&[8.0, 12.0, 9.0, 11.0]).estimate().unwrap();
+        assert_eq!(estimate.samples, 4);
+        assert_eq!(estimate.distance, 10.0);
+        assert_eq!(estimate.spread, 1.5);
+    }
+
+    #[test]
+    fn confidence_counts_a_full_window() {
+        let filter = filter::<4>(&[10.0, 10.0]);
+        assert_eq!(filter.estimate().unwrap().confidence, 0.5);
+        assert!(RangingFilter::<4>::new(3.0, 0.5).estimate().is_none());
+    }
+
+    #[test]
+    fn push_wraps_around_the_window() {
+        let mut filter = filter::<3>(&[1.0, 2.0, 3.0, 40.0, 50.0]);
+        assert_eq!(filter.len(), 3);
+        assert_eq!(filter.head, 2);
+        let mut window = filter.window;
+        window.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(window, [3.0, 40.0, 50.0]);
+        filter.clear();
+        assert!(filter.is_empty());
+    }
+}
@@ -0,0 +1,26 @@
+use crate::ble::ModeBLE;
+use crate::registers::{SX1280Register, SX1280RegisterError};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BleAccessAddress(pub u32);
+
+impl BleAccessAddress {
+    pub const ADVERTISING: Self = Self(0x8E89BED6);
+}
+
+impl TryFrom<[u8; 4]> for BleAccessAddress {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 4]) -> Result<Self, Self::Error> {
+        Ok(Self(u32::from_be_bytes(value)))
+    }
+}
+
+impl SX1280Register<ModeBLE> for BleAccessAddress {
+    const ADDRESS: u16 = 0x9CF;
+    type BufferType = [u8; 4];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()
+    }
+}
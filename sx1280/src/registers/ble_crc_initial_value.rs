@@ -0,0 +1,27 @@
+use crate::ble::ModeBLE;
+use crate::registers::{SX1280Register, SX1280RegisterError};
+
+/// 24 bit CRC initial value, the upper byte is ignored
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BleCrcInitialValue(pub u32);
+
+impl BleCrcInitialValue {
+    pub const ADVERTISING: Self = Self(0x555555);
+}
+
+impl TryFrom<[u8; 3]> for BleCrcInitialValue {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 3]) -> Result<Self, Self::Error> {
+        Ok(Self(u32::from_be_bytes([0, value[0], value[1], value[2]])))
+    }
+}
+
+impl SX1280Register<ModeBLE> for BleCrcInitialValue {
+    const ADDRESS: u16 = 0x9C7;
+    type BufferType = [u8; 3];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()[1..].try_into().unwrap()
+    }
+}
@@ -0,0 +1,53 @@
+use crate::flrc::ModeFLRC;
+use crate::gfsk::ModeGFSK;
+use crate::registers::{SX1280Register, SX1280RegisterError};
+
+/// 16 bit CRC polynomial of GFSK packets
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CrcPolynomial(pub u16);
+
+impl TryFrom<[u8; 2]> for CrcPolynomial {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 2]) -> Result<Self, Self::Error> {
+        Ok(Self(u16::from_be_bytes(value)))
+    }
+}
+
+impl SX1280Register<ModeGFSK> for CrcPolynomial {
+    const ADDRESS: u16 = 0x9C6;
+    type BufferType = [u8; 2];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()
+    }
+}
+
+/// 16 bit CRC seed of GFSK and FLRC packets, BLE uses `BleCrcInitialValue`
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CrcInitialValue(pub u16);
+
+impl TryFrom<[u8; 2]> for CrcInitialValue {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 2]) -> Result<Self, Self::Error> {
+        Ok(Self(u16::from_be_bytes(value)))
+    }
+}
+
+impl SX1280Register<ModeGFSK> for CrcInitialValue {
+    const ADDRESS: u16 = 0x9C8;
+    type BufferType = [u8; 2];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()
+    }
+}
+
+impl SX1280Register<ModeFLRC> for CrcInitialValue {
+    const ADDRESS: u16 = 0x9C8;
+    type BufferType = [u8; 2];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()
+    }
+}
@@ -0,0 +1,23 @@
+use crate::registers::{SX1280Register, SX1280RegisterError};
+use crate::SX1280Mode;
+
+/// Firmware version of the radio, read only
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FirmwareVersion(pub u16);
+
+impl TryFrom<[u8; 2]> for FirmwareVersion {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 2]) -> Result<Self, Self::Error> {
+        Ok(Self(u16::from_be_bytes(value)))
+    }
+}
+
+impl<MODE: SX1280Mode> SX1280Register<MODE> for FirmwareVersion {
+    const ADDRESS: u16 = 0x153;
+    type BufferType = [u8; 2];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()
+    }
+}
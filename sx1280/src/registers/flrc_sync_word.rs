@@ -0,0 +1,27 @@
+use crate::flrc::ModeFLRC;
+use crate::registers::{SX1280Register, SX1280RegisterError};
+
+/// FLRC sync word `INDEX` (1 to 3), selected for matching through `SyncWordMatch`
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FlrcSyncWord<const INDEX: u8>(pub u32);
+
+pub type FlrcSyncWord1 = FlrcSyncWord<1>;
+pub type FlrcSyncWord2 = FlrcSyncWord<2>;
+pub type FlrcSyncWord3 = FlrcSyncWord<3>;
+
+impl<const INDEX: u8> TryFrom<[u8; 4]> for FlrcSyncWord<INDEX> {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 4]) -> Result<Self, Self::Error> {
+        Ok(Self(u32::from_be_bytes(value)))
+    }
+}
+
+impl<const INDEX: u8> SX1280Register<ModeFLRC> for FlrcSyncWord<INDEX> {
+    const ADDRESS: u16 = 0x9CF + 5 * (INDEX as u16 - 1);
+    type BufferType = [u8; 4];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()
+    }
+}
@@ -1,10 +1,10 @@
 use bitfield_struct::{bitfield, FromBits, IntoBits};
-use defmt::Format;
 use num_enum_derive::{FromPrimitive, IntoPrimitive, TryFromPrimitive};
-use crate::sx1280::registers::{SX1280Register, SX1280RegisterError};
-use crate::sx1280::SX1280Mode;
+use crate::registers::{SX1280Register, SX1280RegisterError};
+use crate::SX1280Mode;
 
-#[derive(Clone, Copy, Debug, Format)]
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct FrequencyCompensationMode(pub u8);
 
 impl TryFrom<[u8; 1]> for FrequencyCompensationMode {
@@ -0,0 +1,29 @@
+use crate::gfsk::ModeGFSK;
+use crate::registers::{SX1280Register, SX1280RegisterError};
+
+/// GFSK sync word `INDEX` (1 to 3), 40 bits, selected for matching through `SyncWordMatch`
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GfskSyncWord<const INDEX: u8>(pub u64);
+
+pub type GfskSyncWord1 = GfskSyncWord<1>;
+pub type GfskSyncWord2 = GfskSyncWord<2>;
+pub type GfskSyncWord3 = GfskSyncWord<3>;
+
+impl<const INDEX: u8> TryFrom<[u8; 5]> for GfskSyncWord<INDEX> {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 5]) -> Result<Self, Self::Error> {
+        let mut bytes = [0; 8];
+        bytes[3..].copy_from_slice(&value);
+        Ok(Self(u64::from_be_bytes(bytes)))
+    }
+}
+
+impl<const INDEX: u8> SX1280Register<ModeGFSK> for GfskSyncWord<INDEX> {
+    const ADDRESS: u16 = 0x9CE + 5 * (INDEX as u16 - 1);
+    type BufferType = [u8; 5];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()[3..].try_into().unwrap()
+    }
+}
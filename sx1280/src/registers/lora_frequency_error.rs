@@ -0,0 +1,57 @@
+use crate::commands::set_modulation_parameters::Bandwidth;
+use crate::lora::ModeLoRa;
+#[cfg(feature = "ranging")]
+use crate::ranging::ModeLoRaRanging;
+use crate::registers::{SX1280Register, SX1280RegisterError};
+
+/// Frequency error indicator of the last received LoRa packet, a 20 bit signed value. Read only.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LoRaFrequencyError(pub i32);
+
+impl LoRaFrequencyError {
+    /// Frequency error in Hz for a packet received with `bandwidth`, `1.55 * FEI * BW / 1.6 MHz`
+    pub fn hz(&self, bandwidth: Bandwidth) -> f32 {
+        1.55 * self.0 as f32 * bandwidth.hz() as f32 / 1_600_000.0
+    }
+}
+
+impl TryFrom<[u8; 3]> for LoRaFrequencyError {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 3]) -> Result<Self, Self::Error> {
+        // sign extension of the 20 bit value
+        Ok(Self(i32::from_be_bytes([0, value[0] & 0x0F, value[1], value[2]]) << 12 >> 12))
+    }
+}
+
+impl SX1280Register<ModeLoRa> for LoRaFrequencyError {
+    const ADDRESS: u16 = 0x954;
+    type BufferType = [u8; 3];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()[1..].try_into().unwrap()
+    }
+}
+
+#[cfg(feature = "ranging")]
+impl SX1280Register<ModeLoRaRanging> for LoRaFrequencyError {
+    const ADDRESS: u16 = 0x954;
+    type BufferType = [u8; 3];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()[1..].try_into().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hz_follows_datasheet_formula() {
+        // 1.55 * 16000 * 812500 / 1600000 = 12593.75
+        assert!((LoRaFrequencyError(16_000).hz(Bandwidth::BW812k5Hz) - 12_593.75).abs() < 0.01);
+        // 1.55 * -2000 * 1625000 / 1600000 = -3148.4375
+        assert!((LoRaFrequencyError(-2_000).hz(Bandwidth::BW1M625Hz) + 3_148.4375).abs() < 0.01);
+        assert_eq!(LoRaFrequencyError(0).hz(Bandwidth::BW203k125Hz), 0.0);
+    }
+}
@@ -0,0 +1,23 @@
+use crate::lora::ModeLoRa;
+use crate::registers::{SX1280Register, SX1280RegisterError};
+
+/// Payload length of the next transmitted packet, and of the received one in implicit header mode
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LoRaPayloadLength(pub u8);
+
+impl TryFrom<[u8; 1]> for LoRaPayloadLength {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 1]) -> Result<Self, Self::Error> {
+        Ok(Self(value[0]))
+    }
+}
+
+impl SX1280Register<ModeLoRa> for LoRaPayloadLength {
+    const ADDRESS: u16 = 0x901;
+    type BufferType = [u8; 1];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        [self.0]
+    }
+}
@@ -0,0 +1,38 @@
+use crate::lora::ModeLoRa;
+#[cfg(feature = "ranging")]
+use crate::ranging::ModeLoRaRanging;
+use crate::registers::{SX1280Register, SX1280RegisterError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LoRaSyncWord(pub u16);
+
+impl LoRaSyncWord {
+    pub const PRIVATE: Self = Self(0x1424);
+    pub const PUBLIC: Self = Self(0x3444);
+}
+
+impl TryFrom<[u8; 2]> for LoRaSyncWord {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 2]) -> Result<Self, Self::Error> {
+        Ok(Self(u16::from_be_bytes(value)))
+    }
+}
+
+impl SX1280Register<ModeLoRa> for LoRaSyncWord {
+    const ADDRESS: u16 = 0x944;
+    type BufferType = [u8; 2];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()
+    }
+}
+
+#[cfg(feature = "ranging")]
+impl SX1280Register<ModeLoRaRanging> for LoRaSyncWord {
+    const ADDRESS: u16 = 0x944;
+    type BufferType = [u8; 2];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()
+    }
+}
@@ -0,0 +1,71 @@
+use bitfield_struct::bitfield;
+use crate::registers::{SX1280Register, SX1280RegisterError};
+use crate::SX1280Mode;
+
+/// Clearing `automatic` stops the AGC, the gain is then taken from `LnaGainValue`
+#[bitfield(u8, defmt = cfg(feature = "defmt"))]
+pub struct ManualGainSetting {
+    pub automatic: bool,
+    #[bits(7)] _unknown: u8,
+}
+
+impl TryFrom<[u8; 1]> for ManualGainSetting {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 1]) -> Result<Self, Self::Error> {
+        Ok(Self::from_bits(value[0]))
+    }
+}
+
+impl<MODE: SX1280Mode> SX1280Register<MODE> for ManualGainSetting {
+    const ADDRESS: u16 = 0x895;
+    type BufferType = [u8; 1];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        [self.into_bits()]
+    }
+}
+
+/// LNA gain used with manual gain control, 1 (lowest) to 13 (highest)
+#[bitfield(u8, defmt = cfg(feature = "defmt"))]
+pub struct LnaGainValue {
+    #[bits(4)] pub gain: u8,
+    #[bits(4)] _unknown: u8,
+}
+
+impl TryFrom<[u8; 1]> for LnaGainValue {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 1]) -> Result<Self, Self::Error> {
+        Ok(Self::from_bits(value[0]))
+    }
+}
+
+impl<MODE: SX1280Mode> SX1280Register<MODE> for LnaGainValue {
+    const ADDRESS: u16 = 0x89E;
+    type BufferType = [u8; 1];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        [self.into_bits()]
+    }
+}
+
+#[bitfield(u8, defmt = cfg(feature = "defmt"))]
+pub struct LnaGainControl {
+    #[bits(7)] _unknown: u8,
+    pub manual: bool,
+}
+
+impl TryFrom<[u8; 1]> for LnaGainControl {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 1]) -> Result<Self, Self::Error> {
+        Ok(Self::from_bits(value[0]))
+    }
+}
+
+impl<MODE: SX1280Mode> SX1280Register<MODE> for LnaGainControl {
+    const ADDRESS: u16 = 0x89F;
+    type BufferType = [u8; 1];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        [self.into_bits()]
+    }
+}
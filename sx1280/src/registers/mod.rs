@@ -1,14 +1,28 @@
 pub mod rx_gain;
 pub mod sf_additional_configuration;
 pub mod frequency_compensation_mode;
+pub mod flrc_sync_word;
+pub mod ble_access_address;
+pub mod ble_crc_initial_value;
+pub mod whitening_seed;
+pub mod lora_payload_length;
+pub mod lora_sync_word;
+pub mod lora_frequency_error;
+pub mod gfsk_sync_word;
+pub mod crc;
+pub mod sync_word_tolerance;
+pub mod manual_gain;
+pub mod firmware_version;
+#[cfg(feature = "ranging")]
+pub mod ranging;
 
 use core::error::Error;
 use core::fmt::{Display, Formatter};
-use defmt::Format;
-use crate::sx1280::SX1280Mode;
+use crate::SX1280Mode;
 
 
-#[derive(Clone, Copy, Debug, Format)]
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum SX1280RegisterError{
     NotEnoughData,
     Invalid,
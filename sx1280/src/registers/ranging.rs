@@ -0,0 +1,280 @@
+use bitfield_struct::{bitfield, FromBits, IntoBits};
+use num_enum_derive::{FromPrimitive, IntoPrimitive};
+use crate::commands::set_modulation_parameters::{Bandwidth, SpreadingFactor};
+use crate::ranging::ModeLoRaRanging;
+use crate::registers::{SX1280Register, SX1280RegisterError};
+
+/// Address sent by the master in its ranging request
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RangingRequestAddress(pub u32);
+
+impl TryFrom<[u8; 4]> for RangingRequestAddress {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 4]) -> Result<Self, Self::Error> {
+        Ok(Self(u32::from_be_bytes(value)))
+    }
+}
+
+impl SX1280Register<ModeLoRaRanging> for RangingRequestAddress {
+    const ADDRESS: u16 = 0x912;
+    type BufferType = [u8; 4];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()
+    }
+}
+
+/// Address the slave answers to
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RangingDeviceAddress(pub u32);
+
+impl TryFrom<[u8; 4]> for RangingDeviceAddress {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 4]) -> Result<Self, Self::Error> {
+        Ok(Self(u32::from_be_bytes(value)))
+    }
+}
+
+impl SX1280Register<ModeLoRaRanging> for RangingDeviceAddress {
+    const ADDRESS: u16 = 0x916;
+    type BufferType = [u8; 4];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()
+    }
+}
+
+#[derive(Clone, Copy, Debug, FromPrimitive, IntoPrimitive, IntoBits, FromBits)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum RangingIdCheckLength {
+    #[num_enum(default)]
+    Bits8 = 0,
+    Bits16 = 1,
+    Bits24 = 2,
+    Bits32 = 3,
+}
+
+impl RangingIdCheckLength {
+    pub fn accepts(&self, address: u32) -> bool {
+        match self {
+            RangingIdCheckLength::Bits8 => address <= 0xFF,
+            RangingIdCheckLength::Bits16 => address <= 0xFFFF,
+            RangingIdCheckLength::Bits24 => address <= 0xFFFFFF,
+            RangingIdCheckLength::Bits32 => true,
+        }
+    }
+}
+
+#[bitfield(u8, defmt = cfg(feature = "defmt"))]
+pub struct RangingIdCheck {
+    #[bits(6)] _unknown: u8,
+    #[bits(2)] pub length: RangingIdCheckLength,
+}
+
+impl TryFrom<[u8; 1]> for RangingIdCheck {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 1]) -> Result<Self, Self::Error> {
+        Ok(Self::from_bits(value[0]))
+    }
+}
+
+impl SX1280Register<ModeLoRaRanging> for RangingIdCheck {
+    const ADDRESS: u16 = 0x931;
+    type BufferType = [u8; 1];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        [self.into_bits()]
+    }
+}
+
+/// RX/TX delay calibration, see the datasheet calibration table
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RangingCalibration(pub u16);
+
+impl RangingCalibration {
+    pub fn for_parameters(spreading_factor: SpreadingFactor, bandwidth: Bandwidth) -> Option<Self> {
+        //                          BW406k25Hz  BW812k5Hz  BW1M625Hz
+        const TABLE: [[u16; 3]; 6] = [
+            [10299, 11486, 13308],  // SF5
+            [10271, 11474, 13493],  // SF6
+            [10244, 11453, 13528],  // SF7
+            [10242, 11426, 13515],  // SF8
+            [10230, 11417, 13430],  // SF9
+            [10246, 11401, 13376],  // SF10
+        ];
+        let row = match spreading_factor {
+            SpreadingFactor::SF5 => 0,
+            SpreadingFactor::SF6 => 1,
+            SpreadingFactor::SF7 => 2,
+            SpreadingFactor::SF8 => 3,
+            SpreadingFactor::SF9 => 4,
+            SpreadingFactor::SF10 => 5,
+            _ => return None,
+        };
+        let column = match bandwidth {
+            Bandwidth::BW406k25Hz => 0,
+            Bandwidth::BW812k5Hz => 1,
+            Bandwidth::BW1M625Hz => 2,
+            _ => return None,
+        };
+        Some(Self(TABLE[row][column]))
+    }
+}
+
+impl TryFrom<[u8; 2]> for RangingCalibration {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 2]) -> Result<Self, Self::Error> {
+        Ok(Self(u16::from_be_bytes(value)))
+    }
+}
+
+impl SX1280Register<ModeLoRaRanging> for RangingCalibration {
+    const ADDRESS: u16 = 0x92C;
+    type BufferType = [u8; 2];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()
+    }
+}
+
+#[derive(Clone, Copy, Debug, FromPrimitive, IntoPrimitive, IntoBits, FromBits, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum RangingResultType {
+    #[num_enum(default)]
+    Raw = 0,
+    Averaged = 1,
+    Debiased = 2,
+    Filtered = 3,
+}
+
+#[bitfield(u8, defmt = cfg(feature = "defmt"))]
+pub struct RangingResultConfig {
+    #[bits(4)] _unknown_low: u8,
+    #[bits(2)] pub result_type: RangingResultType,
+    #[bits(2)] _unknown_high: u8,
+}
+
+impl TryFrom<[u8; 1]> for RangingResultConfig {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 1]) -> Result<Self, Self::Error> {
+        Ok(Self::from_bits(value[0]))
+    }
+}
+
+impl SX1280Register<ModeLoRaRanging> for RangingResultConfig {
+    const ADDRESS: u16 = 0x924;
+    type BufferType = [u8; 1];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        [self.into_bits()]
+    }
+}
+
+#[bitfield(u8, defmt = cfg(feature = "defmt"))]
+pub struct RangingResultFreeze {
+    #[bits(1)] _unknown_low: u8,
+    pub freeze: bool,
+    #[bits(6)] _unknown_high: u8,
+}
+
+impl TryFrom<[u8; 1]> for RangingResultFreeze {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 1]) -> Result<Self, Self::Error> {
+        Ok(Self::from_bits(value[0]))
+    }
+}
+
+impl SX1280Register<ModeLoRaRanging> for RangingResultFreeze {
+    const ADDRESS: u16 = 0x97F;
+    type BufferType = [u8; 1];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        [self.into_bits()]
+    }
+}
+
+/// 24 bit ranging result, its meaning depends on the selected `RangingResultType`
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RangingResult(pub u32);
+
+impl RangingResult {
+    pub fn to_meters(&self, result_type: RangingResultType, bandwidth: Bandwidth) -> f32 {
+        match result_type {
+            RangingResultType::Raw => {
+                // distance = complement2(register) * 150 / (2^12 * BW[MHz])
+                let value = ((self.0 << 8) as i32) >> 8;
+                value as f32 * 36621.09375f32 / bandwidth.hz() as f32
+            }
+            _ => self.0 as f32 * 20.0f32 / 100.0f32,
+        }
+    }
+}
+
+impl TryFrom<[u8; 3]> for RangingResult {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 3]) -> Result<Self, Self::Error> {
+        Ok(Self(u32::from_be_bytes([0, value[0], value[1], value[2]])))
+    }
+}
+
+impl SX1280Register<ModeLoRaRanging> for RangingResult {
+    const ADDRESS: u16 = 0x961;
+    type BufferType = [u8; 3];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        self.0.to_be_bytes()[1..].try_into().unwrap()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RangingRssi(pub u8);
+
+impl RangingRssi {
+    pub fn dbm(&self) -> f32 {
+        -(self.0 as f32) / 2.0f32
+    }
+}
+
+impl TryFrom<[u8; 1]> for RangingRssi {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 1]) -> Result<Self, Self::Error> {
+        Ok(Self(value[0]))
+    }
+}
+
+impl SX1280Register<ModeLoRaRanging> for RangingRssi {
+    const ADDRESS: u16 = 0x964;
+    type BufferType = [u8; 1];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        [self.0]
+    }
+}
+
+/// Number of ranging results averaged by the filtered result type, 8 to 255
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RangingFilterWindowSize(pub u8);
+
+impl TryFrom<[u8; 1]> for RangingFilterWindowSize {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 1]) -> Result<Self, Self::Error> {
+        Ok(Self(value[0]))
+    }
+}
+
+impl SX1280Register<ModeLoRaRanging> for RangingFilterWindowSize {
+    const ADDRESS: u16 = 0x91E;
+    type BufferType = [u8; 1];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        [self.0]
+    }
+}
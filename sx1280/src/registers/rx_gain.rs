@@ -1,10 +1,10 @@
 use bitfield_struct::{bitfield, FromBits, IntoBits};
-use defmt::Format;
 use num_enum_derive::{FromPrimitive, IntoPrimitive};
-use crate::sx1280::registers::{SX1280Register, SX1280RegisterError};
-use crate::sx1280::SX1280Mode;
+use crate::registers::{SX1280Register, SX1280RegisterError};
+use crate::SX1280Mode;
 
-#[derive(Clone, Copy, Debug, Format, FromPrimitive, IntoPrimitive, IntoBits, FromBits)]
+#[derive(Clone, Copy, Debug, FromPrimitive, IntoPrimitive, IntoBits, FromBits)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum RxGainSensitivity {
     HighSensitivity = 3,
@@ -14,7 +14,7 @@ pub enum RxGainSensitivity {
     Unknown(u8),
 }
 
-#[bitfield(u8, defmt=true)]
+#[bitfield(u8, defmt = cfg(feature = "defmt"))]
 pub struct RxGain{
     #[bits(6)] _unknown: u8,
     #[bits(2)] pub sensitivity: RxGainSensitivity,
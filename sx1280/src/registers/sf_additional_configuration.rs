@@ -0,0 +1,41 @@
+use bitfield_struct::{bitfield, FromBits, IntoBits};
+use num_enum_derive::TryFromPrimitive;
+use crate::commands::set_modulation_parameters::SpreadingFactor;
+use crate::registers::{SX1280Register, SX1280RegisterError};
+use crate::{SX1280Error, SX1280Mode};
+
+#[derive(Clone, Copy, Debug, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum SFAdditionalConfiguration {
+    SF5_6 = 0x1E,
+    SF7_8 = 0x37,
+    SFOther = 0x32,
+}
+
+impl SFAdditionalConfiguration {
+    /// Value the register must hold for `spreading_factor`, after every LoRa modulation change
+    pub fn for_spreading_factor(spreading_factor: SpreadingFactor) -> Self {
+        match spreading_factor {
+            SpreadingFactor::SF5 | SpreadingFactor::SF6 => SFAdditionalConfiguration::SF5_6,
+            SpreadingFactor::SF7 | SpreadingFactor::SF8 => SFAdditionalConfiguration::SF7_8,
+            _ => SFAdditionalConfiguration::SFOther,
+        }
+    }
+}
+
+impl TryFrom<[u8; 1]> for SFAdditionalConfiguration {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 1]) -> Result<Self, Self::Error> {
+        SFAdditionalConfiguration::try_from(value[0]).map_err(|x| SX1280RegisterError::Invalid)
+    }
+}
+
+impl<MODE: SX1280Mode> SX1280Register<MODE> for SFAdditionalConfiguration {
+    const ADDRESS: u16 = 0x925;
+    type BufferType = [u8; 1];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        [*self as u8]
+    }
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use bitfield_struct::bitfield;
+use crate::flrc::ModeFLRC;
+use crate::gfsk::ModeGFSK;
+use crate::registers::{SX1280Register, SX1280RegisterError};
+
+/// Number of bit errors accepted when matching a sync word
+#[bitfield(u8, defmt = cfg(feature = "defmt"))]
+pub struct SyncWordTolerance {
+    #[bits(4)] pub tolerance: u8,
+    #[bits(4)] _unknown: u8,
+}
+
+impl TryFrom<[u8; 1]> for SyncWordTolerance {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 1]) -> Result<Self, Self::Error> {
+        Ok(Self::from_bits(value[0]))
+    }
+}
+
+impl SX1280Register<ModeGFSK> for SyncWordTolerance {
+    const ADDRESS: u16 = 0x9CD;
+    type BufferType = [u8; 1];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        [self.into_bits()]
+    }
+}
+
+impl SX1280Register<ModeFLRC> for SyncWordTolerance {
+    const ADDRESS: u16 = 0x9CD;
+    type BufferType = [u8; 1];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        [self.into_bits()]
+    }
+}
@@ -0,0 +1,31 @@
+use crate::ble::ModeBLE;
+use crate::gfsk::ModeGFSK;
+use crate::registers::{SX1280Register, SX1280RegisterError};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WhiteningSeed(pub u8);
+
+impl TryFrom<[u8; 1]> for WhiteningSeed {
+    type Error = SX1280RegisterError;
+
+    fn try_from(value: [u8; 1]) -> Result<Self, Self::Error> {
+        Ok(Self(value[0]))
+    }
+}
+
+impl SX1280Register<ModeGFSK> for WhiteningSeed {
+    const ADDRESS: u16 = 0x9C5;
+    type BufferType = [u8; 1];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        [self.0]
+    }
+}
+
+impl SX1280Register<ModeBLE> for WhiteningSeed {
+    const ADDRESS: u16 = 0x9C5;
+    type BufferType = [u8; 1];
+    fn as_write_bytes(&self) -> Self::BufferType {
+        [self.0]
+    }
+}
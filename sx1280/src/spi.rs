@@ -1,11 +1,9 @@
-use defmt::Format;
 use embedded_hal::digital::{ErrorType as PinErrorType, OutputPin};
 use embedded_hal::spi::{ErrorType as SpiErrorType, SpiBus};
-use rp2040_hal::fugit::ExtU64;
-use rtic_monotonics::Monotonic;
-use crate::Mono;
+use embedded_hal_async::delay::DelayNs;
 
-#[derive(Debug, Format, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum SpiError<CS: PinErrorType> {
     PinError(CS::Error),
     AlreadyStarted,
@@ -45,10 +43,10 @@ where
         Ok(SpiDevice { spi, cs: Some(cs) })
     }
 
-    pub async fn start_transaction(&'b mut self) -> SpiResult<SpiTransaction<'a, 'b, SPI, CS>, CS> {
+    pub async fn start_transaction<D: DelayNs>(&'b mut self, delay: &mut D) -> SpiResult<SpiTransaction<'a, 'b, SPI, CS>, CS> {
         let mut cs = self.cs.take().ok_or(SpiError::AlreadyStarted)?;
         cs.set_low().map_err(|x| SpiError::PinError(x))?;
-        Mono::delay(10.micros()).await;
+        delay.delay_us(10).await;
         Ok(SpiTransaction {
             dev_ref: self,
             cs: Some(cs)
@@ -0,0 +1,195 @@
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal::spi::SpiBus;
+use crate::{SX1280Error, SX1280Mode, SX1280ModeValid, SX1280Result, SX1280};
+use crate::commands::get_status::{CircuitMode, GetStatusCommand};
+use crate::commands::set_fs::SetFrequencySynthesisModeCommand;
+use crate::commands::set_rx::{RxPeriod, SetRxModeCommand};
+use crate::commands::set_sleep::SetSleepModeCommand;
+use crate::commands::set_standby::{SetStandbyModeCommand, StandbyMode};
+use crate::commands::set_tx::{SetTxModeCommand, TxPeriod};
+use crate::commands::PeriodBase;
+use crate::dio::SX1280Dio;
+use crate::timer::SX1280Timer;
+
+/// Operating state of the radio, as tracked by the driver
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RadioState {
+    Sleep,
+    StandbyRC,
+    StandbyXOSC,
+    FrequencySynthesis,
+    Tx,
+    Rx,
+    Cad,
+}
+
+impl RadioState {
+    /// Whether the radio may be sent to `next` from this state.
+    /// Sleep is left by waking the radio up, which the driver does on the next SPI access.
+    pub fn can_enter(self, next: RadioState) -> bool {
+        match (self, next) {
+            (RadioState::Sleep, RadioState::StandbyRC) => true,
+            (RadioState::Sleep, _) => false,
+            (RadioState::StandbyRC | RadioState::StandbyXOSC, _) => true,
+            (RadioState::FrequencySynthesis, RadioState::Sleep) => false,
+            (RadioState::FrequencySynthesis, _) => true,
+            (RadioState::Tx | RadioState::Rx | RadioState::Cad, RadioState::StandbyRC | RadioState::StandbyXOSC | RadioState::FrequencySynthesis) => true,
+            // restarts the reception, or the timer of a long preamble
+            (RadioState::Rx, RadioState::Rx) | (RadioState::Tx, RadioState::Tx) => true,
+            (RadioState::Tx | RadioState::Rx | RadioState::Cad, _) => false,
+        }
+    }
+
+    /// States the radio leaves on its own once the operation is done or timed out
+    pub fn is_transient(self) -> bool {
+        matches!(self, RadioState::Tx | RadioState::Rx | RadioState::Cad)
+    }
+}
+
+/// Target of `SX1280::set_mode`, with the arguments of the command entering it
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RadioMode {
+    Sleep(SetSleepModeCommand),
+    Standby(StandbyMode),
+    FrequencySynthesis,
+    Tx(TxPeriod, PeriodBase),
+    Rx(RxPeriod, PeriodBase),
+}
+
+impl RadioMode {
+    pub fn state(&self) -> RadioState {
+        match self {
+            RadioMode::Sleep(_) => RadioState::Sleep,
+            RadioMode::Standby(StandbyMode::StandbyRC) => RadioState::StandbyRC,
+            RadioMode::Standby(StandbyMode::StandbyXOSC) => RadioState::StandbyXOSC,
+            RadioMode::FrequencySynthesis => RadioState::FrequencySynthesis,
+            RadioMode::Tx(_, _) => RadioState::Tx,
+            RadioMode::Rx(_, _) => RadioState::Rx,
+        }
+    }
+}
+
+impl<'a, SPI: SpiBus<u8>, CS: OutputPin, BUSY: InputPin, RESET: OutputPin, DIO: SX1280Dio, TIMER: SX1280Timer, MODE: SX1280Mode> SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, MODE> {
+
+    /// Last state the driver put the radio in, without talking to it
+    pub fn radio_state(&self) -> RadioState {
+        self.state
+    }
+
+    /// Wakes the radio up if it sleeps and checks that it may enter `next`.
+    /// The state is refreshed from the radio before refusing to leave TX, RX or CAD,
+    /// which the radio leaves on its own.
+    pub(crate) async fn __internal_prepare_state(&mut self, next: Option<RadioState>) -> SX1280Result<(), Self> {
+        if self.state == RadioState::Sleep {
+            self.__internal_wake_up().await?;
+        }
+        let Some(next) = next else { return Ok(()) };
+        if !self.state.can_enter(next) && self.state.is_transient() {
+            self.ensure_not_busy()?;
+            let status = self.__internal_command(GetStatusCommand).await?;
+            self.state = state_from_circuit_mode(self.state, status.circuit_mode());
+        }
+        if !self.state.can_enter(next) {
+            return Err(SX1280Error::InvalidTransition(self.state, next));
+        }
+        Ok(())
+    }
+
+    async fn __internal_wake_up(&mut self) -> SX1280Result<(), Self> {
+        // the falling edge of chip select wakes the radio up, anything clocked meanwhile is lost
+        drop(self.spi.start_transaction(&mut self.timer).await?);
+        self.__internal_wait_for_busy().await?;
+        self.state = RadioState::StandbyRC;
+        Ok(())
+    }
+}
+
+impl<'a, SPI: SpiBus<u8>, CS: OutputPin, BUSY: InputPin, RESET: OutputPin, DIO: SX1280Dio, TIMER: SX1280Timer, MODE: SX1280ModeValid> SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, MODE> {
+
+    /// Reads the operating state from the radio. A sleeping radio is not woken up.
+    pub async fn query_state(&mut self) -> SX1280Result<RadioState, Self> {
+        if self.state == RadioState::Sleep {
+            return Ok(RadioState::Sleep);
+        }
+        let status = self.command(GetStatusCommand).await?;
+        self.state = state_from_circuit_mode(self.state, status.circuit_mode());
+        Ok(self.state)
+    }
+
+    pub async fn set_mode(&mut self, mode: RadioMode, timeout: u64) -> SX1280Result<(), Self> {
+        match mode {
+            RadioMode::Sleep(command) => self.command(command).await?,
+            RadioMode::Standby(mode) => self.command(SetStandbyModeCommand { mode }).await?,
+            RadioMode::FrequencySynthesis => self.command(SetFrequencySynthesisModeCommand).await?,
+            RadioMode::Tx(period, period_base) => self.command(SetTxModeCommand { period, period_base }).await?,
+            RadioMode::Rx(period, period_base) => self.command(SetRxModeCommand { period, period_base }).await?,
+        };
+        self.wait_for_busy(timeout).await
+    }
+}
+
+pub(crate) fn state_from_circuit_mode(current: RadioState, mode: CircuitMode) -> RadioState {
+    match mode {
+        CircuitMode::StandbyRC => RadioState::StandbyRC,
+        CircuitMode::StandbyXOSC => RadioState::StandbyXOSC,
+        CircuitMode::FrequencySynthesis => RadioState::FrequencySynthesis,
+        // channel activity detection reports itself as reception
+        CircuitMode::Reception if current == RadioState::Cad => RadioState::Cad,
+        CircuitMode::Reception => RadioState::Rx,
+        CircuitMode::Transmission => RadioState::Tx,
+        CircuitMode::Unknown => current,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{block_on, setup};
+
+    #[test]
+    fn set_mode_tracks_state() {
+        let (sim, mut radio) = setup();
+        assert_eq!(radio.radio_state(), RadioState::StandbyRC);
+        block_on(radio.set_mode(RadioMode::Rx(RxPeriod::Infinite, PeriodBase::Base1ms), 100)).ok().unwrap();
+        assert_eq!(radio.radio_state(), RadioState::Rx);
+        assert_eq!(sim.circuit_mode(), CircuitMode::Reception);
+        assert_eq!(block_on(radio.query_state()).ok().unwrap(), RadioState::Rx);
+    }
+
+    #[test]
+    fn invalid_transition_is_refused() {
+        let (sim, mut radio) = setup();
+        block_on(radio.set_mode(RadioMode::Rx(RxPeriod::Infinite, PeriodBase::Base1ms), 100)).ok().unwrap();
+        sim.clear_frames();
+        let result = block_on(radio.set_mode(RadioMode::Sleep(SetSleepModeCommand::new()), 100));
+        assert!(matches!(result, Err(SX1280Error::InvalidTransition(RadioState::Rx, RadioState::Sleep))));
+        assert!(sim.frames().iter().all(|frame| frame[0] != 0x84));
+        assert!(!sim.is_sleeping());
+    }
+
+    #[test]
+    fn transition_refreshes_finished_operation() {
+        let (sim, mut radio) = setup();
+        block_on(radio.set_mode(RadioMode::Rx(RxPeriod::OneShot, PeriodBase::Base1ms), 100)).ok().unwrap();
+        sim.set_circuit_mode(CircuitMode::StandbyRC);
+        block_on(radio.set_mode(RadioMode::Sleep(SetSleepModeCommand::new()), 100)).ok().unwrap();
+        assert_eq!(radio.radio_state(), RadioState::Sleep);
+        assert!(sim.is_sleeping());
+    }
+
+    #[test]
+    fn access_wakes_radio_from_sleep() {
+        let (sim, mut radio) = setup();
+        block_on(radio.set_mode(RadioMode::Sleep(SetSleepModeCommand::new().with_retain_data_buffer(true)), 100)).ok().unwrap();
+        assert!(sim.is_sleeping());
+        assert_eq!(block_on(radio.query_state()).ok().unwrap(), RadioState::Sleep);
+        assert!(sim.is_sleeping());
+
+        block_on(radio.write_buffer(0, &[1, 2, 3])).ok().unwrap();
+        assert!(!sim.is_sleeping());
+        assert_eq!(radio.radio_state(), RadioState::StandbyRC);
+        assert_eq!(&sim.buffer()[..3], &[1, 2, 3]);
+    }
+}
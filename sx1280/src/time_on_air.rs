@@ -0,0 +1,129 @@
+use crate::commands::set_modulation_parameters::{Bandwidth, CodingRate, SpreadingFactor};
+use crate::commands::set_packet_parameters::{LoRaCrcMode, LoRaHeaderType, LoRaPreambleLength};
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+/// Duration of one LoRa symbol in ns, rounded up
+pub fn symbol_time(spreading_factor: SpreadingFactor, bandwidth: Bandwidth) -> u64 {
+    quarter_symbols_time(spreading_factor, bandwidth, 4)
+}
+
+/// Duration of the preamble and the sync word that follows it in ns, rounded up
+pub fn preamble_time(spreading_factor: SpreadingFactor, bandwidth: Bandwidth, preamble_length: LoRaPreambleLength) -> u64 {
+    quarter_symbols_time(spreading_factor, bandwidth, preamble_quarter_symbols(spreading_factor, preamble_length))
+}
+
+/// Duration of a whole packet on air in ns, rounded up, as given in section 7.4.4 of the datasheet
+pub fn time_on_air(
+    spreading_factor: SpreadingFactor,
+    bandwidth: Bandwidth,
+    coding_rate: CodingRate,
+    preamble_length: LoRaPreambleLength,
+    header_type: LoRaHeaderType,
+    crc_mode: LoRaCrcMode,
+    payload_length: u8,
+) -> u64 {
+    let symbols = preamble_quarter_symbols(spreading_factor, preamble_length)
+        + 4 * data_symbols(spreading_factor, coding_rate, header_type, crc_mode, payload_length);
+    quarter_symbols_time(spreading_factor, bandwidth, symbols)
+}
+
+fn sf(spreading_factor: SpreadingFactor) -> u64 {
+    (spreading_factor as u64) >> 4
+}
+
+fn quarter_symbols_time(spreading_factor: SpreadingFactor, bandwidth: Bandwidth, quarter_symbols: u64) -> u64 {
+    ((quarter_symbols << sf(spreading_factor)) * NANOS_PER_SECOND).div_ceil(4 * bandwidth.hz() as u64)
+}
+
+/// Programmed preamble plus 4.25 sync symbols, 6.25 for SF5 and SF6
+fn preamble_quarter_symbols(spreading_factor: SpreadingFactor, preamble_length: LoRaPreambleLength) -> u64 {
+    let sync = if sf(spreading_factor) <= 6 { 25 } else { 17 };
+    4 * u32::from(preamble_length) as u64 + sync
+}
+
+/// Symbols after the sync word, including the 8 symbols of the header block
+fn data_symbols(spreading_factor: SpreadingFactor, coding_rate: CodingRate, header_type: LoRaHeaderType, crc_mode: LoRaCrcMode, payload_length: u8) -> u64 {
+    let sf = sf(spreading_factor) as i64;
+    // the header block carries full symbols at SF5 and SF6, two bits less per symbol above
+    let short_sf = sf <= 6;
+    // SF11 and SF12 always run with the low data rate optimisation, two bits less per symbol
+    let bits_per_symbol = if sf >= 11 { sf - 2 } else { sf };
+    let header_bits = match header_type {
+        LoRaHeaderType::Explicit => 20,
+        LoRaHeaderType::Implicit => 0,
+    };
+    let crc_bits = match crc_mode {
+        LoRaCrcMode::Enabled => 16,
+        LoRaCrcMode::Disabled => 0,
+    };
+    let payload_bits = 8 * payload_length as i64 + crc_bits;
+
+    let cr = coding_rate as i64;
+    if cr <= 4 {
+        let numerator = payload_bits - 4 * sf + header_bits + if short_sf { 0 } else { 8 };
+        let blocks = div_ceil(numerator.max(0), 4 * bits_per_symbol);
+        return (8 + blocks * (cr + 4)) as u64
+    }
+
+    // long interleaving, 4/5, 4/6 and 4/8 coding over the whole payload after the header block
+    let fec_denominator = if cr == 7 { 8 } else { cr };
+    let bits_per_header_symbol = if short_sf { sf } else { sf - 2 };
+    match header_type {
+        LoRaHeaderType::Explicit => {
+            // what is left of the header block after the header, in whole bytes
+            let header_block_bits = (4 * bits_per_header_symbol - header_bits) & !0x07;
+            let remaining = (payload_bits - header_block_bits).max(0);
+            (8 + div_ceil(remaining * fec_denominator, 4 * bits_per_symbol)) as u64
+        }
+        LoRaHeaderType::Implicit => {
+            let coded_bits = payload_bits * fec_denominator;
+            if coded_bits <= 4 * 7 * bits_per_header_symbol {
+                // short payloads end inside the first block
+                div_ceil(coded_bits, 4 * bits_per_header_symbol) as u64
+            } else {
+                (8 + div_ceil(coded_bits - 4 * 8 * bits_per_header_symbol, 4 * bits_per_symbol)) as u64
+            }
+        }
+    }
+}
+
+fn div_ceil(numerator: i64, denominator: i64) -> i64 {
+    (numerator + denominator - 1) / denominator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lora::LoRaConfig;
+    use crate::lora::tests::lora_config;
+
+    #[test]
+    fn time_on_air_matches_datasheet_formula() {
+        let preamble = |symbols: u32| LoRaPreambleLength::try_from(symbols).ok().unwrap();
+        assert_eq!(symbol_time(SpreadingFactor::SF7, Bandwidth::BW203k125Hz), 630_154);
+        assert_eq!(preamble_time(SpreadingFactor::SF7, Bandwidth::BW203k125Hz, preamble(12)), 10_240_000);
+        assert_eq!(time_on_air(
+            SpreadingFactor::SF7, Bandwidth::BW203k125Hz, CodingRate::CR4_5, preamble(12),
+            LoRaHeaderType::Explicit, LoRaCrcMode::Enabled, 10,
+        ), 27_884_308);
+        assert_eq!(time_on_air(
+            SpreadingFactor::SF5, Bandwidth::BW1M625Hz, CodingRate::CR4_5, preamble(8),
+            LoRaHeaderType::Implicit, LoRaCrcMode::Disabled, 1,
+        ), 438_154);
+        assert_eq!(time_on_air(
+            SpreadingFactor::SF12, Bandwidth::BW812k5Hz, CodingRate::CR4_8, preamble(8),
+            LoRaHeaderType::Explicit, LoRaCrcMode::Enabled, 255,
+        ), 2_158_907_077);
+    }
+
+    #[test]
+    fn time_on_air_long_interleaving() {
+        let config = LoRaConfig { spreading_factor: SpreadingFactor::SF7, preamble_length: 12, ..lora_config() };
+        assert_eq!(LoRaConfig { coding_rate: CodingRate::CR4_5Alt, ..config }.time_on_air(10).ok().unwrap(), 26_624_000);
+        assert!(config.time_on_air(10).ok().unwrap() > LoRaConfig { coding_rate: CodingRate::CR4_5Alt, ..config }.time_on_air(10).ok().unwrap());
+        assert!(LoRaConfig { coding_rate: CodingRate::CR4_8Alt, ..config }.time_on_air(10).ok().unwrap()
+            > LoRaConfig { coding_rate: CodingRate::CR4_6Alt, ..config }.time_on_air(10).ok().unwrap());
+        assert!(LoRaConfig { preamble_length: 17, ..config }.time_on_air(10).is_err());
+    }
+}
@@ -0,0 +1,61 @@
+use core::future::{poll_fn, Future};
+use core::pin::pin;
+use core::task::Poll;
+use embedded_hal_async::delay::DelayNs;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TimeoutError;
+
+/// Runs a future with a deadline of `ms` milliseconds
+#[allow(async_fn_in_trait)]
+pub trait Timeout {
+    async fn timeout_ms<F: Future>(&mut self, ms: u64, future: F) -> Result<F::Output, TimeoutError>;
+}
+
+/// Time source of the driver.
+/// The driver clones it to run a timeout while waiting on itself, so it should be a cheap handle.
+pub trait SX1280Timer: DelayNs + Timeout + Clone {}
+impl<T: DelayNs + Timeout + Clone> SX1280Timer for T {}
+
+/// Turns any `DelayNs` into a `SX1280Timer`, racing the future against a delay
+#[derive(Clone, Copy)]
+pub struct DelayTimeout<D: DelayNs>(pub D);
+
+impl<D: DelayNs> DelayNs for DelayTimeout<D> {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.0.delay_ns(ns).await
+    }
+
+    async fn delay_us(&mut self, us: u32) {
+        self.0.delay_us(us).await
+    }
+
+    async fn delay_ms(&mut self, ms: u32) {
+        self.0.delay_ms(ms).await
+    }
+}
+
+impl<D: DelayNs> Timeout for DelayTimeout<D> {
+    async fn timeout_ms<F: Future>(&mut self, ms: u64, future: F) -> Result<F::Output, TimeoutError> {
+        let mut future = pin!(future);
+        let mut expired = pin!(delay_ms_u64(&mut self.0, ms));
+        poll_fn(|cx| {
+            if let Poll::Ready(output) = future.as_mut().poll(cx) {
+                return Poll::Ready(Ok(output));
+            }
+            if expired.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(Err(TimeoutError));
+            }
+            Poll::Pending
+        }).await
+    }
+}
+
+async fn delay_ms_u64<D: DelayNs>(delay: &mut D, mut ms: u64) {
+    while ms > 0 {
+        let chunk = ms.min(u32::MAX as u64) as u32;
+        delay.delay_ms(chunk).await;
+        ms -= chunk as u64;
+    }
+}
@@ -0,0 +1,155 @@
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal::spi::SpiBus;
+use crate::spi::SpiDevice;
+use crate::{SX1280Mode, SX1280Result, SX1280};
+use crate::ble::ModeBLE;
+use crate::commands::get_packet_type::GetPacketTypeCommand;
+use crate::commands::get_status::GetStatusCommand;
+use crate::commands::set_packet_type::PacketType;
+use crate::flrc::ModeFLRC;
+use crate::gfsk::ModeGFSK;
+use crate::lora::ModeLoRa;
+#[cfg(feature = "ranging")]
+use crate::ranging::ModeLoRaRanging;
+use crate::timer::SX1280Timer;
+use crate::dio::SX1280Dio;
+use crate::state::{state_from_circuit_mode, RadioState};
+use core::marker::PhantomData;
+
+pub struct ModeUninitialized;
+impl SX1280Mode for ModeUninitialized{
+}
+
+/// A radio found already configured by `SX1280::attach`, typed after its packet type
+pub enum AttachedSX1280<'a, SPI: SpiBus<u8>, CS: OutputPin, BUSY: InputPin, RESET: OutputPin, DIO: SX1280Dio, TIMER: SX1280Timer> {
+    GFSK(SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, ModeGFSK>),
+    LoRa(SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, ModeLoRa>),
+    #[cfg(feature = "ranging")]
+    Ranging(SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, ModeLoRaRanging>),
+    FLRC(SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, ModeFLRC>),
+    BLE(SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, ModeBLE>),
+}
+
+impl<'a, SPI: SpiBus<u8>, CS: OutputPin, BUSY: InputPin, RESET: OutputPin, DIO: SX1280Dio, TIMER: SX1280Timer> SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, ModeUninitialized> {
+    pub fn new(spi: SpiDevice<'a, SPI, CS>, busy: BUSY, mut reset: RESET, dio: DIO, timer: TIMER) -> SX1280Result<Self, Self> {
+        reset.set_high()?;
+        Ok(Self{
+            spi,
+            busy_pin: busy,
+            reset_pin: reset,
+            dio,
+            state: RadioState::StandbyRC,
+            timer,
+            check_status: false,
+            tx_base_address: 0,
+            rx_base_address: 0,
+            implicit_header: false,
+            configured: true,
+            _phantom: PhantomData { },
+        })
+    }
+
+    /// Takes over a radio that kept its configuration, e.g. after a reset of the MCU only.
+    /// Reads the operating state and the packet type instead of resetting the radio.
+    /// A radio configured for ranging is refused when the `ranging` feature is disabled.
+    /// The buffer base addresses and header type cannot be read back, so a LoRa radio refuses
+    /// to send or receive with `Unconfigured` until `configure` is called again.
+    pub async fn attach(mut self) -> SX1280Result<AttachedSX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER>, Self> {
+        if self.busy_pin.is_high()? {
+            // most likely asleep, the next access wakes it up
+            self.state = RadioState::Sleep;
+        }
+        let status = self.command(GetStatusCommand).await?;
+        self.state = state_from_circuit_mode(self.state, status.circuit_mode());
+        self.__internal_wait_for_busy().await?;
+        let packet_type = self.command(GetPacketTypeCommand).await?;
+        self.configured = false;
+        Ok(match packet_type {
+            PacketType::GFSK => AttachedSX1280::GFSK(self.__internal_into_mode()),
+            PacketType::LoRa => AttachedSX1280::LoRa(self.__internal_into_mode()),
+            #[cfg(feature = "ranging")]
+            PacketType::Ranging => AttachedSX1280::Ranging(self.__internal_into_mode()),
+            PacketType::FLRC => AttachedSX1280::FLRC(self.__internal_into_mode()),
+            PacketType::BLE => AttachedSX1280::BLE(self.__internal_into_mode()),
+        })
+    }
+
+    fn __internal_into_mode<T: SX1280Mode>(self) -> SX1280<'a, SPI, CS, BUSY, RESET, DIO, TIMER, T> {
+        SX1280 {
+            spi: self.spi,
+            busy_pin: self.busy_pin,
+            reset_pin: self.reset_pin,
+            dio: self.dio,
+            timer: self.timer,
+            state: self.state,
+            check_status: self.check_status,
+            tx_base_address: self.tx_base_address,
+            rx_base_address: self.rx_base_address,
+            implicit_header: self.implicit_header,
+            configured: self.configured,
+            _phantom: PhantomData::<T> { },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{block_on, setup, timer};
+    use crate::commands::set_sleep::SetSleepModeCommand;
+    use crate::dio::NoDio;
+    use crate::lora::tests::lora_config;
+    use crate::state::RadioMode;
+    use crate::SX1280Error;
+
+    #[test]
+    fn attach_detects_packet_type() {
+        let (sim, radio) = setup();
+        drop(radio);
+        sim.clear_frames();
+
+        let mut spi = sim.spi();
+        let dev = SpiDevice::new(&mut spi, sim.chip_select()).ok().unwrap();
+        let radio = SX1280::new(dev, sim.busy_pin(), sim.reset_pin(), NoDio, timer()).ok().unwrap();
+        let attached = block_on(radio.attach()).ok().unwrap();
+        assert!(matches!(attached, AttachedSX1280::LoRa(_)));
+        assert_eq!(sim.frames(), std::vec![std::vec![0xC0], std::vec![0x03, 0x00, 0x00]]);
+    }
+
+    #[test]
+    fn attach_wakes_sleeping_radio() {
+        let (sim, mut radio) = setup();
+        block_on(radio.set_mode(RadioMode::Sleep(SetSleepModeCommand::new().with_retain_ram(true)), 100)).ok().unwrap();
+        drop(radio);
+        assert!(sim.is_sleeping());
+
+        let mut spi = sim.spi();
+        let dev = SpiDevice::new(&mut spi, sim.chip_select()).ok().unwrap();
+        let radio = SX1280::new(dev, sim.busy_pin(), sim.reset_pin(), NoDio, timer()).ok().unwrap();
+        match block_on(radio.attach()).ok().unwrap() {
+            AttachedSX1280::LoRa(radio) => assert_eq!(radio.radio_state(), RadioState::StandbyRC),
+            _ => panic!("expected a LoRa radio"),
+        }
+        assert!(!sim.is_sleeping());
+    }
+
+    #[test]
+    fn attached_radio_refuses_to_transmit_until_configured() {
+        let (sim, radio) = setup();
+        drop(radio);
+
+        let mut spi = sim.spi();
+        let dev = SpiDevice::new(&mut spi, sim.chip_select()).ok().unwrap();
+        let radio = SX1280::new(dev, sim.busy_pin(), sim.reset_pin(), NoDio, timer()).ok().unwrap();
+        let AttachedSX1280::LoRa(mut radio) = block_on(radio.attach()).ok().unwrap() else {
+            panic!("expected a LoRa radio")
+        };
+        sim.clear_frames();
+        assert!(matches!(block_on(radio.transmit(&[1, 2], 100)), Err(SX1280Error::Unconfigured)));
+        assert!(sim.frames().is_empty());
+
+        block_on(radio.configure(&lora_config())).ok().unwrap();
+        block_on(radio.transmit(&[1, 2], 100)).ok().unwrap();
+        assert_eq!(&sim.buffer()[128..130], &[1, 2]);
+    }
+}